@@ -14,7 +14,7 @@ use embassy_time::Timer;
 use {defmt_rtt as _, panic_probe as _};
 
 use npm1300_rs::{
-    buck::BuckVoltage,
+    buck::{BuckId, BuckVoltage},
     gpios::{Gpio, GpioPolarity},
     NPM1300,
 };
@@ -34,7 +34,7 @@ async fn main(_spawner: Spawner) {
 
     let mut npm1300 = NPM1300::new(twi, embassy_time::Delay);
     defmt::info!("Enabling buck 2...");
-    let _ = npm1300.enable_buck2().await;
+    let _ = npm1300.buck(BuckId::Buck2).enable().await;
 
     let buck_status = npm1300.get_buck_status().await;
     defmt::info!("Buck status: {:?}", buck_status);
@@ -43,8 +43,11 @@ async fn main(_spawner: Spawner) {
     Timer::after_millis(5000).await;
 
     defmt::info!("Setting buck 2 voltage to 1.8V...");
-    let _ = npm1300.set_buck2_normal_voltage(BuckVoltage::V1_8).await;
-    let buck2_current_voltage = npm1300.get_buck2_vout_status().await;
+    let _ = npm1300
+        .buck(BuckId::Buck2)
+        .set_normal_voltage(BuckVoltage::V1_8)
+        .await;
+    let buck2_current_voltage = npm1300.buck(BuckId::Buck2).get_vout_status().await;
     defmt::info!("Set buck 2 voltage to {}", buck2_current_voltage);
 
     defmt::info!("Waiting 5s...");
@@ -52,14 +55,15 @@ async fn main(_spawner: Spawner) {
 
     defmt::info!("Setting buck 2 retention voltage to 2.5V...");
     let _ = npm1300
-        .configure_buck2_retention_mode(BuckVoltage::V2_5, Gpio::Gpio0, GpioPolarity::NotInverted)
+        .buck(BuckId::Buck2)
+        .configure_retention_mode(BuckVoltage::V2_5, Gpio::Gpio0, GpioPolarity::NotInverted)
         .await;
     defmt::info!("Waiting 5s...");
     Timer::after_millis(5000).await;
 
     defmt::info!("Enabling PMIC GPIO0...");
     pmic_gpio0.set_high();
-    let buck2_current_voltage = npm1300.get_buck2_vout_status().await;
+    let buck2_current_voltage = npm1300.buck(BuckId::Buck2).get_vout_status().await;
     defmt::info!("Set buck 2 voltage to {}", buck2_current_voltage);
 
     defmt::info!("Waiting 5s...");
@@ -73,7 +77,8 @@ async fn main(_spawner: Spawner) {
 
     defmt::info!("Configuring buck 2 GPIO enable control...");
     let _ = npm1300
-        .set_buck2_gpio_control(Gpio::Gpio1, GpioPolarity::NotInverted)
+        .buck(BuckId::Buck2)
+        .set_gpio_control(Gpio::Gpio1, GpioPolarity::NotInverted)
         .await;
 
     defmt::info!("Waiting 5s...");
@@ -89,7 +94,7 @@ async fn main(_spawner: Spawner) {
     pmic_gpio1.set_high();
 
     defmt::info!("Disabling buck 2...");
-    let _ = npm1300.disable_buck2().await;
+    let _ = npm1300.buck(BuckId::Buck2).disable().await;
 
     loop {
         Timer::after_millis(1000).await;
@@ -0,0 +1,156 @@
+use crate::common::Clock;
+
+/// Number of µA·ms in one mAh
+const UA_MS_PER_MAH: f32 = 3_600_000_000.0;
+
+/// Accumulated charge of a [`CoulombCounter`]
+///
+/// The state can be serialized with [`to_bytes`](Self::to_bytes), stored to flash before
+/// entering ship or hibernate mode, and restored after wake-up with
+/// [`CoulombCounter::restore`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct CoulombCounterState {
+    /// Charge that flowed into the battery in µA·ms
+    pub charged_ua_ms: u64,
+    /// Charge that flowed out of the battery in µA·ms
+    pub discharged_ua_ms: u64,
+}
+
+impl CoulombCounterState {
+    /// Size of the serialized state in bytes
+    pub const SERIALIZED_SIZE: usize = 16;
+
+    /// Serialize the state into little-endian bytes
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut bytes = [0; Self::SERIALIZED_SIZE];
+        bytes[..8].copy_from_slice(&self.charged_ua_ms.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.discharged_ua_ms.to_le_bytes());
+        bytes
+    }
+
+    /// Deserialize a state previously produced by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8; Self::SERIALIZED_SIZE]) -> Self {
+        let mut charged = [0; 8];
+        let mut discharged = [0; 8];
+        charged.copy_from_slice(&bytes[..8]);
+        discharged.copy_from_slice(&bytes[8..]);
+        Self {
+            charged_ua_ms: u64::from_le_bytes(charged),
+            discharged_ua_ms: u64::from_le_bytes(discharged),
+        }
+    }
+
+    /// Charge that flowed into the battery in mAh
+    pub fn charged_mah(&self) -> f32 {
+        self.charged_ua_ms as f32 / UA_MS_PER_MAH
+    }
+
+    /// Charge that flowed out of the battery in mAh
+    pub fn discharged_mah(&self) -> f32 {
+        self.discharged_ua_ms as f32 / UA_MS_PER_MAH
+    }
+
+    /// Net charge in mAh, positive when more charge went into the battery than out of it
+    pub fn net_mah(&self) -> f32 {
+        (self.charged_ua_ms as i128 - self.discharged_ua_ms as i128) as f32 / UA_MS_PER_MAH
+    }
+}
+
+/// Coulomb counter integrating IBAT samples over time
+///
+/// Samples are timestamped with the injected [`Clock`] and integrated with the trapezoidal
+/// rule. The IBAT sign follows [`NPM1300::get_ibat_measurement_result`](crate::NPM1300::get_ibat_measurement_result):
+/// negative while charging, positive while discharging.
+pub struct CoulombCounter<C: Clock> {
+    clock: C,
+    state: CoulombCounterState,
+    /// Timestamp in milliseconds and current in µA of the previous sample
+    last_sample: Option<(u64, i32)>,
+}
+
+impl<C: Clock> CoulombCounter<C> {
+    /// Create a new coulomb counter with no accumulated charge
+    pub fn new(clock: C) -> Self {
+        Self::restore(clock, CoulombCounterState::default())
+    }
+
+    /// Create a coulomb counter from a previously saved state
+    ///
+    /// The time spent between saving and restoring the state is not integrated.
+    pub fn restore(clock: C, state: CoulombCounterState) -> Self {
+        Self {
+            clock,
+            state,
+            last_sample: None,
+        }
+    }
+
+    /// Get the accumulated charge
+    pub fn state(&self) -> CoulombCounterState {
+        self.state
+    }
+
+    /// Integrate up to now and return the state to persist
+    ///
+    /// The previous sample is held until now, and the next sample starts a new integration
+    /// interval. Call this right before entering ship or hibernate mode.
+    pub fn checkpoint(&mut self) -> CoulombCounterState {
+        if let Some((_, current_ua)) = self.last_sample {
+            self.add_sample(current_ua);
+        }
+        self.last_sample = None;
+        self.state
+    }
+
+    /// Add a battery current sample taken now
+    ///
+    /// # Arguments
+    ///
+    /// * `ibat_ua` - Battery current in microamps (µA), negative while charging
+    pub fn add_sample(&mut self, ibat_ua: i32) {
+        let now_ms = self.clock.now_ms();
+        if let Some((last_ms, last_ua)) = self.last_sample {
+            let elapsed_ms = now_ms.saturating_sub(last_ms) as i64;
+            // Trapezoidal rule, halved at the end to keep the precision
+            let charge_2x = (last_ua as i64 + ibat_ua as i64) * elapsed_ms;
+            if charge_2x < 0 {
+                self.state.charged_ua_ms += charge_2x.unsigned_abs() / 2;
+            } else {
+                self.state.discharged_ua_ms += charge_2x as u64 / 2;
+            }
+        }
+        self.last_sample = Some((now_ms, ibat_ua));
+    }
+
+    /// Discard the accumulated charge
+    pub fn reset(&mut self) {
+        self.state = CoulombCounterState::default();
+        self.last_sample = None;
+    }
+
+    /// Read the latest IBAT measurement result and integrate it
+    ///
+    /// Automatic IBAT measurements must be enabled, for instance with
+    /// [`IbatMonitor::start`](crate::adc::IbatMonitor::start) or
+    /// [`NPM1300::configure_auto_ibat_measurement`](crate::NPM1300::configure_auto_ibat_measurement).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(i32))` - The integrated battery current in microamps (µA)
+    /// * `Ok(None)` - The PMIC flagged the measurement as invalid, nothing was integrated
+    /// * `Err(NPM1300Error)` - An error occurred while reading the measurement result
+    pub async fn update<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<Option<i32>, crate::NPM1300Error<I2c::Error>> {
+        let ibat_ua = npm1300.get_ibat_measurement_result().await?;
+        if let Some(ibat_ua) = ibat_ua {
+            self.add_sample(ibat_ua);
+        }
+        Ok(ibat_ua)
+    }
+}
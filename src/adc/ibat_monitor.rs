@@ -0,0 +1,134 @@
+/// ADC event raised when an IBAT measurement result is ready
+const ADC_EVENT_IBAT_READY: u8 = 1 << 6;
+
+/// Interval between ADC event checks while waiting for a measurement in milliseconds
+const EVENT_POLL_INTERVAL_MS: u32 = 10;
+
+/// A single battery current reading produced by [`IbatMonitor`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct IbatReading {
+    /// Latest battery current in microamps (µA), negative while charging
+    pub current_ua: i32,
+    /// Moving average of the last valid readings in microamps (µA)
+    pub average_ua: i32,
+}
+
+/// Continuous battery current monitor
+///
+/// Reads the IBAT result of the automatic VBAT/IBAT measurements taken by the PMIC every second
+/// and keeps a moving average over the last `N` valid readings. Each reading waits for the ADC
+/// IBAT ready event, so no measurement is read twice or skipped.
+///
+/// The monitor does not borrow the driver, so other driver calls such as
+/// [`CoulombCounter::update`](crate::adc::CoulombCounter::update) can run between readings.
+pub struct IbatMonitor<const N: usize> {
+    samples: [i32; N],
+    len: usize,
+    next: usize,
+}
+
+impl<const N: usize> Default for IbatMonitor<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> IbatMonitor<N> {
+    /// Create a new battery current monitor averaging over the last `N` readings
+    pub fn new() -> Self {
+        const { assert!(N > 0, "IBAT monitor needs at least one sample") };
+
+        Self {
+            samples: [0; N],
+            len: 0,
+            next: 0,
+        }
+    }
+
+    /// Start the automatic measurements
+    ///
+    /// Enables automatic VBAT measurements in single mode followed by an automatic IBAT
+    /// measurement.
+    ///
+    /// The charge current and discharge current limit must be configured with
+    /// [`NPM1300::set_charger_current`](crate::NPM1300::set_charger_current) and
+    /// [`NPM1300::set_discharge_current_limit`](crate::NPM1300::set_discharge_current_limit)
+    /// before reading, otherwise [`next`](Self::next) returns
+    /// `NPM1300Error::IbatCurrentLimitsNotConfigured`.
+    pub async fn start<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        // The IBAT result shares the VBAT burst result registers
+        npm1300.configure_vbat_burst_measurement(false).await?;
+        npm1300.clear_adc_event_mask(ADC_EVENT_IBAT_READY).await?;
+        npm1300.configure_auto_ibat_measurement(true).await?;
+        npm1300.configure_auto_vbat_measurement(true).await
+    }
+
+    /// Wait for the next automatic measurement and return the battery current
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(IbatReading))` - The latest reading and the updated moving average
+    /// * `Ok(None)` - The PMIC flagged the measurement as invalid, the average is not updated
+    /// * `Err(NPM1300Error)` - An error occurred while reading the measurement result
+    pub async fn next<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<Option<IbatReading>, crate::NPM1300Error<I2c::Error>> {
+        while npm1300.get_adc_events().await? & ADC_EVENT_IBAT_READY == 0 {
+            npm1300.delay.delay_ms(EVENT_POLL_INTERVAL_MS).await;
+        }
+        npm1300.clear_adc_event_mask(ADC_EVENT_IBAT_READY).await?;
+
+        let Some(current_ua) = npm1300.get_ibat_measurement_result().await? else {
+            return Ok(None);
+        };
+
+        self.samples[self.next] = current_ua;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        Ok(Some(IbatReading {
+            current_ua,
+            average_ua: self.average_ua().unwrap_or(current_ua),
+        }))
+    }
+
+    /// Get the moving average of the last valid readings in microamps (µA)
+    ///
+    /// Returns `None` if no valid reading has been taken yet.
+    pub fn average_ua(&self) -> Option<i32> {
+        if self.len == 0 {
+            return None;
+        }
+        let sum: i64 = self.samples[..self.len].iter().map(|&s| s as i64).sum();
+        Some((sum / self.len as i64) as i32)
+    }
+
+    /// Discard all readings accumulated in the moving average
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+
+    /// Stop the automatic VBAT and IBAT measurements
+    pub async fn stop<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        npm1300.configure_auto_ibat_measurement(false).await?;
+        npm1300.configure_auto_vbat_measurement(false).await
+    }
+}
@@ -1,7 +1,14 @@
-use crate::charger::DischargeCurrentLimit;
+use crate::charger::{die_temperature_celsius, DischargeCurrentLimit};
 use crate::common::Task;
 use crate::{NtcThermistorType, Ntcautotim, Tempautotim, Vbatautoenable, Vbatburstenable};
-use libm::logf;
+
+mod coulomb_counter;
+mod ibat_monitor;
+mod thermistor;
+
+pub use coulomb_counter::*;
+pub use ibat_monitor::*;
+pub use thermistor::*;
 
 const ADC_CONVERSION_TIME_US: u32 = 250;
 
@@ -179,12 +186,24 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
 
     /// Measure NTC
     ///
+    /// Converts the measurement with the Beta value set by
+    /// [`configure_ntc_resistance`](Self::configure_ntc_resistance). Use
+    /// [`measure_ntc_with_model`](Self::measure_ntc_with_model) for other thermistor models.
+    ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The measured NTC resistance in degrees Celsius
+    /// * `Ok(f32)` - The measured NTC temperature in degrees Celsius
     /// * `Err(NPM1300Error)` - An error occurred while reading the NTC measurement result
     //TODO: test this function
     pub async fn measure_ntc(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        self.trigger_ntc_measurement().await?;
+
+        let result = self.get_ntc_measurement_result().await?;
+        Ok(result)
+    }
+
+    /// Trigger an NTC measurement and wait for it to complete
+    async fn trigger_ntc_measurement(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         #[cfg(feature = "defmt-03")]
         defmt::debug!("Triggering NTC measurement...");
         self.device
@@ -197,9 +216,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         #[cfg(feature = "defmt-03")]
         defmt::debug!("Waiting for measurement to complete...");
         self.delay.delay_us(ADC_CONVERSION_TIME_US).await;
-
-        let result = self.get_ntc_measurement_result().await?;
-        Ok(result)
+        Ok(())
     }
 
     /// Get the latest NTC measurement result without triggering a new measurement
@@ -216,6 +233,14 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     pub async fn get_ntc_measurement_result(
         &mut self,
     ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        let model = self.get_ntc_beta_model().await?;
+
+        // The temperature is returned in degrees Celsius
+        self.get_ntc_measurement_result_with_model(&model).await
+    }
+
+    /// Get the latest raw 10-bit NTC measurement result
+    async fn get_ntc_measurement_code(&mut self) -> Result<u16, crate::NPM1300Error<I2c::Error>> {
         let msb = self
             .device
             .adc()
@@ -231,17 +256,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await?
             .ntcresultlsb();
         // Convert result to u16
-        let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
-        // Convert result to f32
-        // The temperature is returned in degrees Celsius
-        //
-        let result = if let Some(ntc_beta) = self.ntc_beta {
-            1.0 / ((1.0 / 298.15) - (1.0 / ntc_beta) * logf((1024.0 / result as f32) - 1.0))
-                - 273.15
-        } else {
-            return Err(crate::NPM1300Error::InvalidNtcBeta);
-        };
-        Ok(result)
+        Ok(((msb as u16) << 2) | (lsb & 0x03) as u16)
     }
 
     /// Measure die temperature
@@ -304,11 +319,8 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
 
-        // Convert result to f32
         // The temperature is returned in degrees Celsius
-        let result = 394.67 - 0.7926 * result as f32;
-
-        Ok(result)
+        Ok(die_temperature_celsius(result))
     }
 
     /// Measure VSYS
@@ -427,7 +439,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         delay: u16,
     ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
         // Test if delay is valid
-        if !(4..=514).contains(&delay) || delay % 2 != 0 {
+        if !(4..=514).contains(&delay) || !delay.is_multiple_of(2) {
             return Err(crate::NPM1300Error::InvalidVbatMeasurementDelayValue);
         }
         // Calculate register delay
@@ -494,6 +506,26 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         discharge_current_limit: DischargeCurrentLimit,
         charge_current_limit_ma: u16,
     ) -> Result<i32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .measure_ibat(discharge_current_limit, charge_current_limit_ma)
+            .await?
+            .unwrap_or(0))
+    }
+
+    /// Trigger a VBAT measurement and read the IBAT measurement taken after it
+    ///
+    /// Requires auto IBAT measurement, see
+    /// [`configure_auto_ibat_measurement`](Self::configure_auto_ibat_measurement).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(i32))` - The battery current in microamps (µA), negative while charging
+    /// * `Ok(None)` - The PMIC flagged the measurement as invalid
+    pub(crate) async fn measure_ibat(
+        &mut self,
+        discharge_current_limit: DischargeCurrentLimit,
+        charge_current_limit_ma: u16,
+    ) -> Result<Option<i32>, crate::NPM1300Error<I2c::Error>> {
         self.device
             .adc()
             .taskvbatmeasure()
@@ -502,10 +534,59 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
 
         self.delay.delay_us(ADC_CONVERSION_TIME_US).await;
 
+        self.read_ibat_result(discharge_current_limit, charge_current_limit_ma)
+            .await
+    }
+
+    /// Get the latest IBAT measurement result without triggering a new measurement
+    ///
+    /// This function retrieves the most recent IBAT measurement result from the ADC registers.
+    /// It is primarily intended for use with automatic IBAT measurements (see
+    /// [`configure_auto_ibat_measurement`](Self::configure_auto_ibat_measurement)), where an IBAT
+    /// measurement is taken after every VBAT measurement.
+    /// Delays must be handled by the caller.
+    ///
+    /// The conversion uses the charge current and discharge current limit cached by
+    /// [`set_charger_current`](Self::set_charger_current) and
+    /// [`set_discharge_current_limit`](Self::set_discharge_current_limit).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(i32))` - The battery current in microamps (µA), negative while charging
+    /// * `Ok(None)` - The PMIC flagged the measurement as invalid
+    /// * `Err(NPM1300Error::IbatCurrentLimitsNotConfigured)` - The charge current or discharge
+    ///   current limit has not been set through this driver
+    /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
+    pub async fn get_ibat_measurement_result(
+        &mut self,
+    ) -> Result<Option<i32>, crate::NPM1300Error<I2c::Error>> {
+        let (Some(discharge_current_limit), Some(charge_current_ma)) =
+            (self.discharge_current_limit, self.charge_current_ma)
+        else {
+            return Err(crate::NPM1300Error::IbatCurrentLimitsNotConfigured);
+        };
+
+        self.read_ibat_result(discharge_current_limit, charge_current_ma)
+            .await
+    }
+
+    /// Read and convert the IBAT measurement result
+    ///
+    /// The IBAT result is stored in the third VBAT burst result register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(i32))` - The battery current in microamps (µA), negative while charging
+    /// * `Ok(None)` - The PMIC flagged the measurement as invalid
+    async fn read_ibat_result(
+        &mut self,
+        discharge_current_limit: DischargeCurrentLimit,
+        charge_current_limit_ma: u16,
+    ) -> Result<Option<i32>, crate::NPM1300Error<I2c::Error>> {
         let st = self.device.adc().adcibatmeasstatus().read_async().await?;
         let mode = st.bchargermode();
         if st.batmeaseinvalid() == 1 {
-            return Ok(0);
+            return Ok(None);
         }
 
         let msb = self
@@ -537,11 +618,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
                 (ichg_ma * 1250, -1)
             }
             1 | 2 => (idis_ma * 1120, 1),
-            _ => return Ok(0),
+            _ => return Ok(Some(0)),
         };
 
         let ibat_ua = (full_scale_ua as i64 * code as i64) / 1023;
-        Ok((sign as i64 * ibat_ua) as i32)
+        Ok(Some((sign as i64 * ibat_ua) as i32))
     }
 
     /// Configure auto VBAT measurement
@@ -0,0 +1,525 @@
+use libm::{cbrtf, expf, logf, sqrtf};
+
+use crate::charger::{ntc_threshold_code, NtcThresholdRegion};
+use crate::NtcThermistorType;
+
+/// 0°C in Kelvin
+const ZERO_CELSIUS_KELVIN: f32 = 273.15;
+
+/// Full scale of the 10-bit NTC measurement and thresholds
+const NTC_FULL_SCALE: f32 = 1024.0;
+
+/// Resistance/temperature characteristic of an NTC thermistor
+pub trait ThermistorModel {
+    /// Convert a thermistor resistance in ohms to a temperature in degrees Celsius
+    fn temperature_celsius(&self, resistance_ohms: f32) -> f32;
+
+    /// Convert a temperature in degrees Celsius to a thermistor resistance in ohms
+    fn resistance_ohms(&self, temperature_celsius: f32) -> f32;
+}
+
+/// Beta (B-parameter) thermistor model
+///
+/// `1/T = 1/T25 + 1/B * ln(R/R25)`
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BetaModel {
+    /// Resistance at 25°C in ohms
+    pub r25_ohms: f32,
+    /// Beta value in Kelvin
+    pub beta: f32,
+}
+
+impl ThermistorModel for BetaModel {
+    fn temperature_celsius(&self, resistance_ohms: f32) -> f32 {
+        1.0 / ((1.0 / (25.0 + ZERO_CELSIUS_KELVIN))
+            + (1.0 / self.beta) * logf(resistance_ohms / self.r25_ohms))
+            - ZERO_CELSIUS_KELVIN
+    }
+
+    fn resistance_ohms(&self, temperature_celsius: f32) -> f32 {
+        let t = temperature_celsius + ZERO_CELSIUS_KELVIN;
+        self.r25_ohms * expf(self.beta * (1.0 / t - 1.0 / (25.0 + ZERO_CELSIUS_KELVIN)))
+    }
+}
+
+/// Steinhart–Hart thermistor model
+///
+/// `1/T = A + B * ln(R) + C * ln(R)^3`
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct SteinhartHartModel {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+}
+
+impl ThermistorModel for SteinhartHartModel {
+    fn temperature_celsius(&self, resistance_ohms: f32) -> f32 {
+        let ln_r = logf(resistance_ohms);
+        1.0 / (self.a + self.b * ln_r + self.c * ln_r * ln_r * ln_r) - ZERO_CELSIUS_KELVIN
+    }
+
+    fn resistance_ohms(&self, temperature_celsius: f32) -> f32 {
+        // Closed-form inverse of the Steinhart–Hart equation
+        let y = (self.a - 1.0 / (temperature_celsius + ZERO_CELSIUS_KELVIN)) / self.c;
+        let b_3c = self.b / (3.0 * self.c);
+        let x = sqrtf(b_3c * b_3c * b_3c + y * y / 4.0);
+        expf(cbrtf(x - y / 2.0) - cbrtf(x + y / 2.0))
+    }
+}
+
+/// Lookup table thermistor model
+///
+/// Linearly interpolates the temperature against the logarithm of the resistance between
+/// the points of a table, typically taken from the thermistor datasheet. Values outside of
+/// the table are clamped to its first and last points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct LookupTableModel<'a> {
+    /// `(temperature in °C, resistance in ohms)` points sorted by ascending temperature
+    table: &'a [(f32, f32)],
+}
+
+impl<'a> LookupTableModel<'a> {
+    /// Create a lookup table model
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - `(temperature in °C, resistance in ohms)` points sorted by ascending
+    ///   temperature. Must contain at least one point.
+    pub fn new(table: &'a [(f32, f32)]) -> Self {
+        Self { table }
+    }
+
+    /// Find the table segment containing `value` and interpolate along it
+    ///
+    /// `key` extracts the interpolation input from a point and `output` the result. Points are
+    /// sorted so that `key` is monotonic; `ascending` tells in which direction.
+    fn interpolate(
+        &self,
+        value: f32,
+        ascending: bool,
+        key: impl Fn(&(f32, f32)) -> f32,
+        output: impl Fn(&(f32, f32)) -> f32,
+    ) -> f32 {
+        let (Some(first), Some(last)) = (self.table.first(), self.table.last()) else {
+            return f32::NAN;
+        };
+        let before = |a: f32, b: f32| if ascending { a <= b } else { a >= b };
+
+        if before(value, key(first)) {
+            return output(first);
+        }
+        for segment in self.table.windows(2) {
+            let (lo, hi) = (&segment[0], &segment[1]);
+            if before(value, key(hi)) {
+                let ratio = (value - key(lo)) / (key(hi) - key(lo));
+                return output(lo) + ratio * (output(hi) - output(lo));
+            }
+        }
+        output(last)
+    }
+}
+
+impl ThermistorModel for LookupTableModel<'_> {
+    fn temperature_celsius(&self, resistance_ohms: f32) -> f32 {
+        // NTC resistance decreases with temperature
+        self.interpolate(logf(resistance_ohms), false, |&(_, r)| logf(r), |&(t, _)| t)
+    }
+
+    fn resistance_ohms(&self, temperature_celsius: f32) -> f32 {
+        expf(self.interpolate(temperature_celsius, true, |&(t, _)| t, |&(_, r)| logf(r)))
+    }
+}
+
+impl NtcThermistorType {
+    /// Nominal resistance at 25°C in ohms, or `None` if no thermistor is used
+    ///
+    /// This is also the value of the PMIC internal bias resistor for the selected type.
+    pub fn nominal_resistance_ohms(&self) -> Option<f32> {
+        match self {
+            NtcThermistorType::None => None,
+            NtcThermistorType::Ntc10K => Some(10_000.0),
+            NtcThermistorType::Ntc47K => Some(47_000.0),
+            NtcThermistorType::Ntc100K => Some(100_000.0),
+        }
+    }
+}
+
+/// NTC threshold regions from the coldest to the hottest
+const NTC_THRESHOLD_REGIONS: [NtcThresholdRegion; 4] = [
+    NtcThresholdRegion::Cold,
+    NtcThresholdRegion::Cool,
+    NtcThresholdRegion::Warm,
+    NtcThresholdRegion::Hot,
+];
+
+/// NTC threshold temperatures in degrees Celsius
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct NtcThresholdsCelsius {
+    pub cold: f32,
+    pub cool: f32,
+    pub warm: f32,
+    pub hot: f32,
+}
+
+/// Position of a region in [`NTC_THRESHOLD_REGIONS`]
+fn threshold_region_index(region: NtcThresholdRegion) -> usize {
+    match region {
+        NtcThresholdRegion::Cold => 0,
+        NtcThresholdRegion::Cool => 1,
+        NtcThresholdRegion::Warm => 2,
+        NtcThresholdRegion::Hot => 3,
+    }
+}
+
+/// Thermistor resistance in ohms at a threshold temperature, if it is a valid resistance
+fn threshold_resistance(model: &impl ThermistorModel, temperature_celsius: f32) -> Option<u32> {
+    let resistance = model.resistance_ohms(temperature_celsius);
+    (resistance.is_finite() && resistance >= 0.0).then_some(resistance as u32)
+}
+
+/// Convert a 10-bit NTC code to a thermistor resistance in ohms
+fn ntc_code_to_resistance(code: u16, nominal_resistance_ohms: f32) -> f32 {
+    nominal_resistance_ohms * code as f32 / (NTC_FULL_SCALE - code as f32)
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Get the nominal resistance of the configured NTC thermistor in ohms
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::NtcThermistorNotConfigured` if no thermistor is selected.
+    async fn get_ntc_nominal_resistance(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        self.get_ntc_resistance_configuration()
+            .await?
+            .nominal_resistance_ohms()
+            .ok_or(crate::NPM1300Error::NtcThermistorNotConfigured)
+    }
+
+    /// Get the latest NTC measurement as a thermistor resistance without triggering a new measurement
+    ///
+    /// Delays must be handled by the caller.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f32)` - The most recent thermistor resistance in ohms
+    /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
+    pub async fn get_ntc_resistance_measurement_result(
+        &mut self,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        let nominal_resistance = self.get_ntc_nominal_resistance().await?;
+        let code = self.get_ntc_measurement_code().await?;
+        Ok(ntc_code_to_resistance(code, nominal_resistance))
+    }
+
+    /// Measure NTC using a thermistor model
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The thermistor model used for the conversion, see [`ThermistorModel`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f32)` - The measured NTC temperature in degrees Celsius
+    /// * `Err(NPM1300Error)` - An error occurred while reading the NTC measurement result
+    pub async fn measure_ntc_with_model(
+        &mut self,
+        model: &impl ThermistorModel,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        self.trigger_ntc_measurement().await?;
+        self.get_ntc_measurement_result_with_model(model).await
+    }
+
+    /// Get the latest NTC measurement result using a thermistor model without triggering a new measurement
+    ///
+    /// Delays must be handled by the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The thermistor model used for the conversion, see [`ThermistorModel`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f32)` - The most recent NTC temperature in degrees Celsius
+    /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
+    pub async fn get_ntc_measurement_result_with_model(
+        &mut self,
+        model: &impl ThermistorModel,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        let resistance = self.get_ntc_resistance_measurement_result().await?;
+        Ok(model.temperature_celsius(resistance))
+    }
+
+    /// Set an NTC temperature threshold in degrees Celsius using a thermistor model
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The temperature region to set the threshold for (Cold, Cool, Warm, or Hot)
+    /// * `temperature_celsius` - The threshold temperature in degrees Celsius
+    /// * `model` - The thermistor model used for the conversion, see [`ThermistorModel`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidNtcThreshold` if the computed threshold is outside the 10-bit allowable range
+    pub async fn set_ntc_threshold_with_model(
+        &mut self,
+        region: NtcThresholdRegion,
+        temperature_celsius: f32,
+        model: &impl ThermistorModel,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let nominal_resistance = self.get_ntc_nominal_resistance().await? as u32;
+        let resistance = threshold_resistance(model, temperature_celsius)
+            .ok_or(crate::NPM1300Error::InvalidNtcThreshold)?;
+        let code = ntc_threshold_code(resistance, nominal_resistance)
+            .ok_or(crate::NPM1300Error::InvalidNtcThreshold)?;
+
+        // Check the new threshold against the ones already configured
+        let index = threshold_region_index(region);
+        for (other_index, other_region) in NTC_THRESHOLD_REGIONS.into_iter().enumerate() {
+            if other_index == index {
+                continue;
+            }
+            let other_code = self.get_ntc_threshold(other_region).await?;
+            // Colder thresholds have higher resistances, hence higher codes
+            let is_ordered = if other_index < index {
+                other_code > code
+            } else {
+                other_code < code
+            };
+            if !is_ordered {
+                return Err(crate::NPM1300Error::InvalidNtcThresholdOrder);
+            }
+        }
+
+        self.set_ntc_threshold(region, resistance, nominal_resistance)
+            .await
+    }
+
+    /// Set all NTC temperature thresholds in degrees Celsius using a thermistor model
+    ///
+    /// Unlike [`set_ntc_threshold_with_model`](Self::set_ntc_threshold_with_model), the thresholds
+    /// are only validated against each other, so a complete new set can be written regardless of
+    /// the thresholds currently configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `thresholds` - The cold, cool, warm and hot threshold temperatures in degrees Celsius
+    /// * `model` - The thermistor model used for the conversion, see [`ThermistorModel`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidNtcThresholdOrder` if the thresholds are not strictly increasing,
+    /// or `NPM1300Error::InvalidNtcThreshold` if a computed threshold is outside the 10-bit allowable range
+    pub async fn set_ntc_thresholds_with_model(
+        &mut self,
+        thresholds: NtcThresholdsCelsius,
+        model: &impl ThermistorModel,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let temperatures = [
+            thresholds.cold,
+            thresholds.cool,
+            thresholds.warm,
+            thresholds.hot,
+        ];
+        if !temperatures.windows(2).all(|pair| pair[0] < pair[1]) {
+            return Err(crate::NPM1300Error::InvalidNtcThresholdOrder);
+        }
+
+        let nominal_resistance = self.get_ntc_nominal_resistance().await? as u32;
+        let mut resistances = [0; 4];
+        for (resistance, temperature) in resistances.iter_mut().zip(temperatures) {
+            *resistance = threshold_resistance(model, temperature)
+                .filter(|&r| ntc_threshold_code(r, nominal_resistance).is_some())
+                .ok_or(crate::NPM1300Error::InvalidNtcThreshold)?;
+        }
+
+        for (region, resistance) in NTC_THRESHOLD_REGIONS.into_iter().zip(resistances) {
+            self.set_ntc_threshold(region, resistance, nominal_resistance)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Get a configured NTC threshold in degrees Celsius using a thermistor model
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The temperature region to get the threshold for (Cold, Cool, Warm, or Hot)
+    /// * `model` - The thermistor model used for the conversion, see [`ThermistorModel`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f32)` - The threshold temperature in degrees Celsius
+    /// * `Err(NPM1300Error)` - An error occurred while reading the NTC threshold
+    pub async fn get_ntc_threshold_with_model(
+        &mut self,
+        region: NtcThresholdRegion,
+        model: &impl ThermistorModel,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        let nominal_resistance = self.get_ntc_nominal_resistance().await?;
+        let code = self.get_ntc_threshold(region).await?;
+        Ok(model.temperature_celsius(ntc_code_to_resistance(code, nominal_resistance)))
+    }
+
+    /// Get the Beta model of the configured NTC thermistor
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidNtcBeta` if no Beta value was set with
+    /// [`configure_ntc_resistance`](Self::configure_ntc_resistance), or
+    /// `NPM1300Error::NtcThermistorNotConfigured` if no thermistor is selected.
+    pub(crate) async fn get_ntc_beta_model(
+        &mut self,
+    ) -> Result<BetaModel, crate::NPM1300Error<I2c::Error>> {
+        let Some(beta) = self.ntc_beta else {
+            return Err(crate::NPM1300Error::InvalidNtcBeta);
+        };
+        let r25_ohms = self.get_ntc_nominal_resistance().await?;
+        Ok(BetaModel { r25_ohms, beta })
+    }
+
+    /// Set an NTC temperature threshold in degrees Celsius
+    ///
+    /// Converts the temperature with the thermistor type and Beta value set by
+    /// [`configure_ntc_resistance`](Self::configure_ntc_resistance).
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The temperature region to set the threshold for (Cold, Cool, Warm, or Hot)
+    /// * `temperature_celsius` - The threshold temperature in degrees Celsius
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidNtcThresholdOrder` if the threshold breaks the
+    /// cold < cool < warm < hot ordering with the thresholds already configured, or
+    /// `NPM1300Error::InvalidNtcThreshold` if the computed threshold is outside the 10-bit allowable range
+    pub async fn set_ntc_threshold_celsius(
+        &mut self,
+        region: NtcThresholdRegion,
+        temperature_celsius: f32,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let model = self.get_ntc_beta_model().await?;
+        self.set_ntc_threshold_with_model(region, temperature_celsius, &model)
+            .await
+    }
+
+    /// Set all NTC temperature thresholds in degrees Celsius
+    ///
+    /// Converts the temperatures with the thermistor type and Beta value set by
+    /// [`configure_ntc_resistance`](Self::configure_ntc_resistance).
+    ///
+    /// # Arguments
+    ///
+    /// * `thresholds` - The cold, cool, warm and hot threshold temperatures in degrees Celsius
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidNtcThresholdOrder` if the thresholds are not strictly increasing,
+    /// or `NPM1300Error::InvalidNtcThreshold` if a computed threshold is outside the 10-bit allowable range
+    pub async fn set_ntc_thresholds_celsius(
+        &mut self,
+        thresholds: NtcThresholdsCelsius,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let model = self.get_ntc_beta_model().await?;
+        self.set_ntc_thresholds_with_model(thresholds, &model).await
+    }
+
+    /// Get a configured NTC threshold in degrees Celsius
+    ///
+    /// Converts the threshold with the thermistor type and Beta value set by
+    /// [`configure_ntc_resistance`](Self::configure_ntc_resistance).
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The temperature region to get the threshold for (Cold, Cool, Warm, or Hot)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f32)` - The threshold temperature in degrees Celsius
+    /// * `Err(NPM1300Error)` - An error occurred while reading the NTC threshold
+    pub async fn get_ntc_threshold_celsius(
+        &mut self,
+        region: NtcThresholdRegion,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        let model = self.get_ntc_beta_model().await?;
+        self.get_ntc_threshold_with_model(region, &model).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Resistances of a 10 kΩ thermistor spanning the JEITA temperature range
+    const RESISTANCES_OHMS: [f32; 5] = [95_000.0, 32_000.0, 10_000.0, 4_000.0, 1_500.0];
+
+    const TABLE: [(f32, f32); 5] = [
+        (-20.0, 97_070.0),
+        (0.0, 32_650.0),
+        (25.0, 10_000.0),
+        (50.0, 3_602.0),
+        (80.0, 1_256.0),
+    ];
+
+    fn assert_round_trip(model: &impl ThermistorModel) {
+        for resistance_ohms in RESISTANCES_OHMS {
+            let temperature_celsius = model.temperature_celsius(resistance_ohms);
+            let round_trip_ohms = model.resistance_ohms(temperature_celsius);
+            assert!(
+                (round_trip_ohms - resistance_ohms).abs() / resistance_ohms < 1e-3,
+                "{resistance_ohms} Ω -> {temperature_celsius} °C -> {round_trip_ohms} Ω"
+            );
+        }
+    }
+
+    #[test]
+    fn beta_model_round_trip() {
+        let model = BetaModel {
+            r25_ohms: 10_000.0,
+            beta: 3380.0,
+        };
+        assert!((model.temperature_celsius(10_000.0) - 25.0).abs() < 1e-3);
+        assert_round_trip(&model);
+    }
+
+    #[test]
+    fn steinhart_hart_model_round_trip() {
+        let model = SteinhartHartModel {
+            a: 1.009_249_5e-3,
+            b: 2.378_405_4e-4,
+            c: 2.019_202_7e-7,
+        };
+        assert_round_trip(&model);
+    }
+
+    #[test]
+    fn lookup_table_model_round_trip() {
+        let model = LookupTableModel::new(&TABLE);
+        assert_round_trip(&model);
+        for (temperature_celsius, resistance_ohms) in TABLE {
+            assert!(
+                (model.temperature_celsius(resistance_ohms) - temperature_celsius).abs() < 1e-3
+            );
+        }
+    }
+
+    #[test]
+    fn lookup_table_model_clamps_at_table_ends() {
+        let model = LookupTableModel::new(&TABLE);
+        assert_eq!(model.temperature_celsius(200_000.0), -20.0);
+        assert_eq!(model.temperature_celsius(500.0), 80.0);
+        assert!((model.resistance_ohms(-40.0) - 97_070.0).abs() < 1.0);
+        assert!((model.resistance_ohms(100.0) - 1_256.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn empty_lookup_table_model_is_nan() {
+        let model = LookupTableModel::new(&[]);
+        assert!(model.temperature_celsius(10_000.0).is_nan());
+    }
+}
@@ -1,10 +1,8 @@
 use crate::{
     common::Task,
-    field_sets::Buckstatus,
-    field_sets::Buckvoutstatus,
-    gpios::{Gpio, GpioMode, GpioPolarity},
-    Buck1Autoctrlsel, Buck1Enpulldown, Buck1Swctrlsel, Buck2Autoctrlsel, Buck2Enpulldown,
-    Buck2Swctrlsel,
+    gpios::{Gpio, GpioFunction, GpioMode, GpioPolarity},
+    Buck1Autoctrlsel, Buck1Enpulldown, Buck1Mode, Buck1Pwmok, Buck1Pwrgood, Buck1Swctrlsel,
+    Buck2Autoctrlsel, Buck2Enpulldown, Buck2Mode, Buck2Pwmok, Buck2Pwrgood, Buck2Swctrlsel,
 };
 
 mod types;
@@ -12,12 +10,15 @@ mod types;
 // Re-export everything in types.rs
 pub use types::*;
 
-/// Convert a GPIO enum value to its register index
+/// Interval between power-good checks in milliseconds
+const POWER_GOOD_POLL_INTERVAL_MS: u32 = 1;
+
+/// Decode a BUCK voltage register field
 ///
-/// GPIOs are 1-indexed in the nPM1300 so we subtract 1 from the GPIO number
-/// to get the register index
-fn gpio_to_register_index(gpio: Gpio) -> usize {
-    usize::from(u8::from(gpio) - 1)
+/// Codes above the highest BUCK output voltage select 3.3 V, so a failed conversion
+/// saturates there instead of being treated as an error.
+fn saturating_buck_voltage(voltage: Result<BuckVoltage, ()>) -> BuckVoltage {
+    voltage.unwrap_or(BuckVoltage::V3_30)
 }
 
 pub struct Config {
@@ -36,526 +37,807 @@ impl Default for Config {
     }
 }
 
+/// Handle to one of the BUCK regulators
+///
+/// Created with [`NPM1300::buck`](crate::NPM1300::buck).
+pub struct Buck<'a, I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs> {
+    npm1300: &'a mut crate::NPM1300<I2c, Delay>,
+    id: BuckId,
+}
+
 impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
+    Buck<'_, I2c, Delay>
 {
-    /// Enable or disable a BUCK regulator
-    ///
-    /// # Arguments
-    ///
-    /// * `buck_index` - Index of the BUCK regulator (0 for BUCK1, 1 for BUCK2)
-    /// * `enable` - true to enable the regulator, false to disable it
-    async fn control_buck_power(
-        &mut self,
-        buck_index: u8,
-        enable: bool,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        if enable {
-            // Enable the regulator
-            self.device
-                .buck()
-                .buckenaset(buck_index.into())
-                .dispatch_async(|command| command.set_taskbuckenaset(Task::Trigger))
-                .await
-        } else {
-            // Disable the regulator
-            self.device
-                .buck()
-                .buckenaclr(buck_index.into())
-                .dispatch_async(|command| command.set_taskbuckenaclr(Task::Trigger))
-                .await
-        }
+    /// Get the BUCK regulator this handle controls
+    pub fn id(&self) -> BuckId {
+        self.id
     }
 
-    /// Enable BUCK1
-    pub async fn enable_buck1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(0, true).await
+    /// Enable the BUCK regulator
+    pub async fn enable(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.npm1300
+            .device
+            .buck()
+            .buckenaset(self.id.index())
+            .dispatch_async(|command| command.set_taskbuckenaset(Task::Trigger))
+            .await
     }
 
-    /// Disable BUCK1
-    pub async fn disable_buck1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(0, false).await
+    /// Disable the BUCK regulator
+    pub async fn disable(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.npm1300
+            .device
+            .buck()
+            .buckenaclr(self.id.index())
+            .dispatch_async(|command| command.set_taskbuckenaclr(Task::Trigger))
+            .await
     }
 
-    /// Enable BUCK2
-    pub async fn enable_buck2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(1, true).await
+    /// Enable forced PWM mode
+    pub async fn enable_forced_pwm_mode(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.npm1300
+            .device
+            .buck()
+            .buckpwmset(self.id.index())
+            .dispatch_async(|command| command.set_taskbuckpwmset(Task::Trigger))
+            .await
     }
 
-    /// Disable BUCK2
-    pub async fn disable_buck2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(1, false).await
+    /// Disable forced PWM mode and return to auto mode
+    pub async fn disable_forced_pwm_mode(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.npm1300
+            .device
+            .buck()
+            .buckpwmclr(self.id.index())
+            .dispatch_async(|command| command.set_taskbuckpwmclr(Task::Trigger))
+            .await
     }
 
-    /// Enable or disable forced PWM mode for a BUCK regulator
+    /// Set the normal mode output voltage
     ///
     /// # Arguments
     ///
-    /// * `buck_index` - Index of the BUCK regulator (0 for BUCK1, 1 for BUCK2)
-    /// * `enable` - true to enable forced PWM mode, false to return to auto mode
-    async fn set_buck_forced_pwm_mode(
+    /// * `voltage` - The voltage to set. See [`BuckVoltage`] for available values.
+    pub async fn set_normal_voltage(
         &mut self,
-        buck_index: u8,
-        enable: bool,
+        voltage: BuckVoltage,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        if enable {
-            // Enable forced PWM mode
-            self.device
-                .buck()
-                .buckpwmset(buck_index.into())
-                .dispatch_async(|command| command.set_taskbuckpwmset(Task::Trigger))
-                .await
-        } else {
-            // Disable forced PWM mode
-            self.device
-                .buck()
-                .buckpwmclr(buck_index.into())
-                .dispatch_async(|command| command.set_taskbuckpwmclr(Task::Trigger))
-                .await
+        // Set normal mode output voltage
+        match self.id {
+            BuckId::Buck1 => {
+                self.npm1300
+                    .device
+                    .buck()
+                    .buck_1_normvout()
+                    .write_async(|reg| reg.set_value(voltage))
+                    .await?
+            }
+            BuckId::Buck2 => {
+                self.npm1300
+                    .device
+                    .buck()
+                    .buck_2_normvout()
+                    .write_async(|reg| reg.set_value(voltage))
+                    .await?
+            }
         }
+        // Allow SW to override VSET pin
+        self.set_software_control(true).await
     }
 
-    /// Enable BUCK1 forced PWM mode
-    pub async fn enable_buck1_forced_pwm_mode(
+    /// Set the normal mode output voltage in millivolts
+    ///
+    /// The voltage is rounded to the closest 100 mV step.
+    ///
+    /// # Arguments
+    ///
+    /// * `millivolts` - The voltage to set in millivolts, between 1000 and 3300 mV
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidBuckVoltage` if the voltage is outside the 1.0-3.3 V range
+    pub async fn set_voltage_mv(
         &mut self,
+        millivolts: u16,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(0, true).await
+        let voltage = BuckVoltage::from_mv_rounded(millivolts)
+            .ok_or(crate::NPM1300Error::InvalidBuckVoltage(millivolts))?;
+        self.set_normal_voltage(voltage).await
     }
 
-    /// Disable BUCK1 forced PWM mode and return to auto mode
-    pub async fn disable_buck1_forced_pwm_mode(
+    /// Ramp the normal mode output voltage to a target voltage in millivolts
+    ///
+    /// Moves the voltage from the current output voltage to the target in steps of
+    /// `config.step_mv`, waiting `config.dwell_us` after each step and verifying that the
+    /// regulator reports power-good before taking the next step.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_mv` - The target voltage in millivolts, rounded to the closest 100 mV step
+    /// * `config` - The step size and dwell time, see [`BuckRampConfig`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidBuckVoltage` if the target is outside the 1.0-3.3 V range,
+    /// `NPM1300Error::InvalidBuckRampStep` if the step is not a non-zero multiple of 100 mV,
+    /// `NPM1300Error::BuckDisabled` if the regulator is not powered before the ramp starts, or
+    /// `NPM1300Error::BuckPowerNotGood` if power-good is not reported after a step. The ramp
+    /// stops at the step that failed.
+    pub async fn ramp_voltage_mv(
         &mut self,
+        target_mv: u16,
+        config: BuckRampConfig,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(0, false).await
-    }
+        let target_mv = BuckVoltage::from_mv_rounded(target_mv)
+            .ok_or(crate::NPM1300Error::InvalidBuckVoltage(target_mv))?
+            .into();
+        if config.step_mv == 0 || !config.step_mv.is_multiple_of(BuckVoltage::STEP_MV) {
+            return Err(crate::NPM1300Error::InvalidBuckRampStep(config.step_mv));
+        }
 
-    /// Enable BUCK2 forced PWM mode
-    pub async fn enable_buck2_forced_pwm_mode(
-        &mut self,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(1, true).await
+        // A disabled rail never reports power-good, so refuse before writing the first step
+        if !self.is_power_good().await? {
+            return Err(crate::NPM1300Error::BuckDisabled(self.id));
+        }
+        // Start from the actual output, which differs from the normal mode voltage under VSET
+        // pin or retention control
+        let mut voltage_mv: u16 = self.get_vout_status().await?.into();
+        loop {
+            voltage_mv = if target_mv > voltage_mv {
+                voltage_mv.saturating_add(config.step_mv).min(target_mv)
+            } else {
+                voltage_mv.saturating_sub(config.step_mv).max(target_mv)
+            };
+            // Every step lands on a valid voltage since the start, target and step are all
+            // multiples of 100 mV within range
+            let voltage = BuckVoltage::try_from(voltage_mv)
+                .map_err(|_| crate::NPM1300Error::InvalidBuckVoltage(voltage_mv))?;
+            self.set_normal_voltage(voltage).await?;
+
+            self.npm1300.delay.delay_us(config.dwell_us).await;
+            if !self.is_power_good().await? {
+                return Err(crate::NPM1300Error::BuckPowerNotGood(self.id));
+            }
+
+            if voltage_mv == target_mv {
+                return Ok(());
+            }
+        }
     }
 
-    /// Disable BUCK2 forced PWM mode and return to auto mode
-    pub async fn disable_buck2_forced_pwm_mode(
-        &mut self,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(1, false).await
+    /// Check whether the regulator reports power-good
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - `true` if the regulator is powered and its output is good
+    /// * `Err(NPM1300Error)` - An error occurred while reading the BUCK status
+    pub async fn is_power_good(&mut self) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        self.npm1300.is_buck_power_good(self.id).await
     }
 
-    /// Set BUCK1 normal mode output voltage
+    /// Get the output voltage in millivolts
     ///
-    /// # Arguments
+    /// Decodes the VOUT status, which reflects the voltage currently selected by the
+    /// regulator, whether set by software, the VSET pin or the retention GPIO.
+    ///
+    /// # Returns
+    /// * `Ok(u16)` - The output voltage in millivolts
+    /// * `Err(NPM1300Error)` - An error occurred while reading the VOUT status
+    pub async fn get_voltage_mv(&mut self) -> Result<u16, crate::NPM1300Error<I2c::Error>> {
+        Ok(self.get_vout_status().await?.into())
+    }
+
+    /// Get the normal mode output voltage
     ///
-    /// * `voltage` - The voltage to set for BUCK1. See [`BuckVoltage`] for available values.
-    pub async fn set_buck1_normal_voltage(
+    /// # Returns
+    /// * `Ok(BuckVoltage)` - The normal mode output voltage
+    /// * `Err(NPM1300Error)` - An error occurred while reading the voltage register
+    pub async fn get_normal_voltage(
         &mut self,
-        voltage: BuckVoltage,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        // Set BUCK1 normal mode output voltage
-        self.device
-            .buck()
-            .buck_1_normvout()
-            .write_async(|reg| reg.set_value(voltage))
-            .await?;
-        // Allow SW to override VSET pin
-        self.device
-            .buck()
-            .buckswctrlsel()
-            .modify_async(|reg| reg.set_buck_1_swctrlsel(Buck1Swctrlsel::Swctrl))
-            .await
+    ) -> Result<BuckVoltage, crate::NPM1300Error<I2c::Error>> {
+        let voltage = match self.id {
+            BuckId::Buck1 => self
+                .npm1300
+                .device
+                .buck()
+                .buck_1_normvout()
+                .read_async()
+                .await?
+                .value(),
+            BuckId::Buck2 => self
+                .npm1300
+                .device
+                .buck()
+                .buck_2_normvout()
+                .read_async()
+                .await?
+                .value(),
+        };
+        Ok(saturating_buck_voltage(voltage))
     }
 
-    /// Allow VSET pins to set BUCK1 VOUT
-    pub async fn enable_buck1_vset_voltage(
+    /// Get the retention mode output voltage
+    ///
+    /// # Returns
+    /// * `Ok(BuckVoltage)` - The retention mode output voltage
+    /// * `Err(NPM1300Error)` - An error occurred while reading the voltage register
+    pub async fn get_retention_voltage(
         &mut self,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .buck()
-            .buckswctrlsel()
-            .modify_async(|reg| reg.set_buck_1_swctrlsel(Buck1Swctrlsel::Vsetandswctrl))
-            .await
+    ) -> Result<BuckVoltage, crate::NPM1300Error<I2c::Error>> {
+        let voltage = match self.id {
+            BuckId::Buck1 => self
+                .npm1300
+                .device
+                .buck()
+                .buck_1_retvout()
+                .read_async()
+                .await?
+                .value(),
+            BuckId::Buck2 => self
+                .npm1300
+                .device
+                .buck()
+                .buck_2_retvout()
+                .read_async()
+                .await?
+                .value(),
+        };
+        Ok(saturating_buck_voltage(voltage))
+    }
+
+    /// Allow the VSET pin to set VOUT
+    pub async fn enable_vset_voltage(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.set_software_control(false).await
     }
 
-    /// Allow VSET pins to set BUCK2 VOUT
-    pub async fn enable_buck2_vset_voltage(
+    /// Select whether software overrides the VSET pin
+    ///
+    /// # Arguments
+    ///
+    /// * `enable` - true to let software set VOUT, false to let the VSET pin set VOUT
+    async fn set_software_control(
         &mut self,
+        enable: bool,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
+        let id = self.id;
+        self.npm1300
+            .device
             .buck()
             .buckswctrlsel()
-            .modify_async(|reg| reg.set_buck_2_swctrlsel(Buck2Swctrlsel::Vsetandswctrl))
+            .modify_async(|reg| match (id, enable) {
+                (BuckId::Buck1, true) => reg.set_buck_1_swctrlsel(Buck1Swctrlsel::Swctrl),
+                (BuckId::Buck1, false) => reg.set_buck_1_swctrlsel(Buck1Swctrlsel::Vsetandswctrl),
+                (BuckId::Buck2, true) => reg.set_buck_2_swctrlsel(Buck2Swctrlsel::Swctrl),
+                (BuckId::Buck2, false) => reg.set_buck_2_swctrlsel(Buck2Swctrlsel::Vsetandswctrl),
+            })
             .await
     }
 
-    /// Set BUCK2 normal mode output voltage
-    pub async fn set_buck2_normal_voltage(
+    /// Configure a GPIO as input for a BUCK control function
+    ///
+    /// The GPIO is only checked for availability here. Callers claim it with
+    /// [`claim_gpio`](crate::NPM1300::claim_gpio) once the BUCK registers are written, so that
+    /// an I2C error leaves the GPIO ownership unchanged.
+    async fn configure_control_gpio(
         &mut self,
-        voltage: BuckVoltage,
+        gpio: Gpio,
+        function: GpioFunction,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        // Set BUCK2 normal mode output voltage
-        self.device
-            .buck()
-            .buck_2_normvout()
-            .write_async(|reg| reg.set_value(voltage))
-            .await?;
-        // Allow SW to override VSET pin
-        self.device
-            .buck()
-            .buckswctrlsel()
-            .modify_async(|reg| reg.set_buck_2_swctrlsel(Buck2Swctrlsel::Swctrl))
-            .await
+        self.npm1300.check_gpio_available(gpio, function)?;
+        if let Some(index) = gpio.index() {
+            self.npm1300
+                .device
+                .gpios()
+                .gpiomode(index)
+                .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
+                .await?;
+        }
+        Ok(())
     }
 
-    /// Configure retention mode for a BUCK regulator
+    /// Configure retention mode
     ///
     /// # Arguments
     ///
-    /// * `buck_index` - Index of the BUCK regulator (1 for BUCK1, 2 for BUCK2)
-    /// * `voltage` - The voltage to set for the BUCK. See [`BuckVoltage`] for available values.
+    /// * `voltage` - The retention voltage. See [`BuckVoltage`] for available values.
     /// * `gpio` - The GPIO to set for retention mode. See [`Gpio`] for available values.
     /// * `polarity` - The polarity of the GPIO. See [`GpioPolarity`] for available values.
-    async fn configure_buck_retention_mode(
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function
+    pub async fn configure_retention_mode(
         &mut self,
-        buck_index: u8,
         voltage: BuckVoltage,
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        // Configure GPIO mode as input
-        self.device
-            .gpios()
-            .gpiomode(gpio_to_register_index(gpio))
-            .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
+        // Assign the GPIO and configure it as input
+        self.configure_control_gpio(gpio, GpioFunction::BuckRetention(self.id))
             .await?;
         // Set retention mode GPIO and its polarity
-        self.device
+        let id = self.id;
+        self.npm1300
+            .device
             .buck()
             .buckvretctrl()
-            .modify_async(|reg| match buck_index {
-                1 => {
+            .modify_async(|reg| match id {
+                BuckId::Buck1 => {
                     reg.set_buck_1_vretgpisel(gpio);
                     reg.set_buck_1_vretgpiinv(polarity);
                 }
-                2 => {
+                BuckId::Buck2 => {
                     reg.set_buck_2_vretgpisel(gpio);
                     reg.set_buck_2_vretgpiinv(polarity);
                 }
-                _ => panic!("Invalid BUCK index"),
             })
             .await?;
+        self.npm1300
+            .claim_gpio(gpio, GpioFunction::BuckRetention(id))?;
         // Set retention mode output voltage
-        match buck_index {
-            1 => {
-                self.device
+        match self.id {
+            BuckId::Buck1 => {
+                self.npm1300
+                    .device
                     .buck()
                     .buck_1_retvout()
                     .write_async(|reg| reg.set_value(voltage))
                     .await?
             }
-            2 => {
-                self.device
+            BuckId::Buck2 => {
+                self.npm1300
+                    .device
                     .buck()
                     .buck_2_retvout()
                     .write_async(|reg| reg.set_value(voltage))
                     .await?
             }
-            _ => panic!("Invalid BUCK index"),
         }
         // Allow SW to override VSET pin
-        self.device
+        self.set_software_control(true).await
+    }
+
+    /// Disable retention mode
+    pub async fn disable_retention(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let id = self.id;
+        self.npm1300
+            .device
             .buck()
-            .buckswctrlsel()
-            .modify_async(|reg| match buck_index {
-                1 => reg.set_buck_1_swctrlsel(Buck1Swctrlsel::Swctrl),
-                2 => reg.set_buck_2_swctrlsel(Buck2Swctrlsel::Swctrl),
-                _ => panic!("Invalid BUCK index"),
+            .buckvretctrl()
+            .modify_async(|reg| match id {
+                BuckId::Buck1 => reg.set_buck_1_vretgpisel(Gpio::None),
+                BuckId::Buck2 => reg.set_buck_2_vretgpisel(Gpio::None),
             })
-            .await
+            .await?;
+        self.npm1300
+            .release_gpio_function(GpioFunction::BuckRetention(id));
+        Ok(())
     }
 
-    /// Configure BUCK1 retention mode
+    /// Configure GPIO enable control
+    ///
+    /// Passing [`Gpio::None`] disables the GPIO control and releases the GPIO previously
+    /// assigned to it.
     ///
     /// # Arguments
+    /// * `gpio` - GPIO to enable
+    /// * `polarity` - Polarity of GPIO
     ///
-    /// * `voltage` - The voltage to set for the BUCK. See [`BuckVoltage`] for available values.
-    /// * `gpio` - The GPIO to set for retention mode. See [`Gpio`] for available values.
-    /// * `polarity` - The polarity of the GPIO. See [`GpioPolarity`] for available values.
-    pub async fn configure_buck1_retention_mode(
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function
+    pub async fn set_gpio_control(
         &mut self,
-        voltage: BuckVoltage,
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_retention_mode(1, voltage, gpio, polarity)
-            .await
+        // Assign the GPIO and configure it as input
+        self.configure_control_gpio(gpio, GpioFunction::BuckEnable(self.id))
+            .await?;
+
+        // Configure or release the GPIO and its polarity
+        let id = self.id;
+        self.npm1300
+            .device
+            .buck()
+            .buckenctrl()
+            .modify_async(|reg| match id {
+                BuckId::Buck1 => {
+                    reg.set_buck_1_engpisel(gpio);
+                    reg.set_buck_1_engpiinv(polarity);
+                }
+                BuckId::Buck2 => {
+                    reg.set_buck_2_engpisel(gpio);
+                    reg.set_buck_2_engpiinv(polarity);
+                }
+            })
+            .await?;
+        self.npm1300.claim_gpio(gpio, GpioFunction::BuckEnable(id))
     }
 
-    /// Configure BUCK2 retention mode
+    /// Configure GPIO forced PWM mode control
+    ///
+    /// Passing [`Gpio::None`] disables the GPIO control and releases the GPIO previously
+    /// assigned to it.
     ///
     /// # Arguments
+    /// * `gpio` - GPIO to enable
+    /// * `polarity` - Polarity of GPIO
     ///
-    /// * `voltage` - The voltage to set for the BUCK. See [`BuckVoltage`] for available values.
-    /// * `gpio` - The GPIO to set for retention mode. See [`Gpio`] for available values.
-    /// * `polarity` - The polarity of the GPIO. See [`GpioPolarity`] for available values.
-    pub async fn configure_buck2_retention_mode(
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function
+    pub async fn set_gpio_forced_pwm_mode_control(
         &mut self,
-        voltage: BuckVoltage,
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_retention_mode(2, voltage, gpio, polarity)
-            .await
-    }
-
-    /// Disable BUCK1 retention mode
-    pub async fn disable_buck1_retention(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .buck()
-            .buckvretctrl()
-            .modify_async(|reg| reg.set_buck_1_vretgpisel(Gpio::None))
-            .await
-    }
+        // Assign the GPIO and configure it as input
+        self.configure_control_gpio(gpio, GpioFunction::BuckForcedPwm(self.id))
+            .await?;
 
-    /// Disable BUCK2 retention mode
-    pub async fn disable_buck2_retention(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
+        // Configure or release the GPIO and its polarity
+        let id = self.id;
+        self.npm1300
+            .device
             .buck()
-            .buckvretctrl()
-            .modify_async(|reg| reg.set_buck_2_vretgpisel(Gpio::None))
-            .await
+            .buckpwmctrl()
+            .modify_async(|reg| match id {
+                BuckId::Buck1 => {
+                    reg.set_buck_1_pwmgpisel(gpio);
+                    reg.set_buck_1_pwmgpiinv(polarity);
+                }
+                BuckId::Buck2 => {
+                    reg.set_buck_2_pwmgpisel(gpio);
+                    reg.set_buck_2_pwmgpiinv(polarity);
+                }
+            })
+            .await?;
+        self.npm1300
+            .claim_gpio(gpio, GpioFunction::BuckForcedPwm(id))
     }
 
-    /// Configure BUCK GPIO enable control
+    /// Get VOUT status
     ///
-    /// # Arguments
-    /// * `buck_index` - BUCK index
-    /// * `gpio` - GPIO to enable
-    /// * `polarity` - Polarity of GPIO
-    async fn configure_buck_gpio_enable_control(
+    /// Note: the current voltage output setting can be read, but it is not measured by an ADC.
+    ///
+    /// # Returns
+    /// * `Ok(BuckVoltage)` - The output voltage currently selected by the regulator
+    /// * `Err(NPM1300Error)` - An error occurred while reading the VOUT status
+    pub async fn get_vout_status(
         &mut self,
-        buck_index: u8,
-        gpio: Gpio,
-        polarity: GpioPolarity,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        if gpio != Gpio::None {
-            // Configure GPIO mode as input
-            self.device
-                .gpios()
-                .gpiomode(gpio_to_register_index(gpio))
-                .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
-                .await?;
+    ) -> Result<BuckVoltage, crate::NPM1300Error<I2c::Error>> {
+        self.npm1300.get_buck_vout_status(self.id).await
+    }
 
-            // Configure GPIO and its polarity
-            self.device
-                .buck()
-                .buckenctrl()
-                .modify_async(|reg| match buck_index {
-                    1 => {
-                        reg.set_buck_1_engpisel(gpio);
-                        reg.set_buck_1_engpiinv(polarity);
-                    }
-                    2 => {
-                        reg.set_buck_2_engpisel(gpio);
-                        reg.set_buck_2_engpiinv(polarity);
-                    }
-                    _ => panic!("Invalid BUCK index"),
-                })
-                .await?;
-        }
-        Ok(())
+    /// Get the regulator status
+    ///
+    /// # Returns
+    /// * `Ok(RailStatus)` - The mode, power-good, PWM-ok and output voltage of the regulator
+    /// * `Err(NPM1300Error)` - An error occurred while reading the BUCK status
+    pub async fn get_status(&mut self) -> Result<RailStatus, crate::NPM1300Error<I2c::Error>> {
+        Ok(self.npm1300.get_buck_status().await?.rail(self.id))
     }
 
-    /// Configure BUCK1 GPIO enable control
+    /// Wait until the regulator reports power-good
     ///
-    /// # Arguments
-    /// * `gpio` - GPIO to enable
-    /// * `polarity` - Polarity of GPIO
-    pub async fn set_buck1_gpio_control(
+    /// See [`NPM1300::wait_for_buck_power_good`](crate::NPM1300::wait_for_buck_power_good).
+    pub async fn wait_for_power_good(
         &mut self,
-        gpio: Gpio,
-        polarity: GpioPolarity,
+        timeout_ms: u32,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_gpio_enable_control(1, gpio, polarity)
+        self.npm1300
+            .wait_for_buck_power_good(self.id, timeout_ms)
             .await
     }
 
-    /// Configure BUCK2 GPIO enable control
+    /// Configure operating mode
     ///
     /// # Arguments
-    /// * `gpio` - GPIO to enable
-    /// * `polarity` - Polarity of GPIO
-    pub async fn set_buck2_gpio_control(
+    /// * `mode` - The operating mode to set, see [`BuckOperatingMode`] for available values.
+    pub async fn configure_operating_mode(
         &mut self,
-        gpio: Gpio,
-        polarity: GpioPolarity,
+        mode: BuckOperatingMode,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_gpio_enable_control(2, gpio, polarity)
+        let id = self.id;
+        self.npm1300
+            .device
+            .buck()
+            .buckctrl_0()
+            .modify_async(|reg| match (id, mode) {
+                (BuckId::Buck1, BuckOperatingMode::Auto) => {
+                    reg.set_buck_1_autoctrlsel(Buck1Autoctrlsel::Auto)
+                }
+                (BuckId::Buck1, BuckOperatingMode::Pfm) => {
+                    reg.set_buck_1_autoctrlsel(Buck1Autoctrlsel::Pfm)
+                }
+                (BuckId::Buck2, BuckOperatingMode::Auto) => {
+                    reg.set_buck_2_autoctrlsel(Buck2Autoctrlsel::Auto)
+                }
+                (BuckId::Buck2, BuckOperatingMode::Pfm) => {
+                    reg.set_buck_2_autoctrlsel(Buck2Autoctrlsel::Pfm)
+                }
+            })
             .await
     }
 
-    /// Configure BUCK GPIO forced PWM mode control
+    /// Set the effective regulator mode
+    ///
+    /// Writes the auto/PFM selection, the forced PWM tasks and the forced PWM GPIO control
+    /// so that they agree on the requested mode. Any previous forced PWM GPIO control is
+    /// released unless the mode is [`BuckMode::GpioControlledPwm`].
     ///
     /// # Arguments
-    /// * `buck_index` - BUCK index
-    /// * `gpio` - GPIO to enable
-    /// * `polarity` - Polarity of GPIO
-    async fn configure_buck_forced_pwm_mode_control(
+    /// * `mode` - The mode to set, see [`BuckMode`] for available values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function
+    pub async fn set_mode(
         &mut self,
-        buck_index: u8,
-        gpio: Gpio,
-        polarity: GpioPolarity,
+        mode: BuckMode,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let (operating_mode, forced_pwm, gpio, polarity) = match mode {
+            BuckMode::Auto => (
+                BuckOperatingMode::Auto,
+                false,
+                Gpio::None,
+                GpioPolarity::NotInverted,
+            ),
+            BuckMode::Pfm => (
+                BuckOperatingMode::Pfm,
+                false,
+                Gpio::None,
+                GpioPolarity::NotInverted,
+            ),
+            BuckMode::ForcedPwm => (
+                BuckOperatingMode::Auto,
+                true,
+                Gpio::None,
+                GpioPolarity::NotInverted,
+            ),
+            BuckMode::GpioControlledPwm(gpio, polarity) => {
+                (BuckOperatingMode::Auto, false, gpio, polarity)
+            }
+        };
+
+        // Assign the GPIO and configure it as input
+        self.configure_control_gpio(gpio, GpioFunction::BuckForcedPwm(self.id))
+            .await?;
+        // Set or release the forced PWM GPIO and its polarity
+        let id = self.id;
+        self.npm1300
+            .device
+            .buck()
+            .buckpwmctrl()
+            .modify_async(|reg| match id {
+                BuckId::Buck1 => {
+                    reg.set_buck_1_pwmgpisel(gpio);
+                    reg.set_buck_1_pwmgpiinv(polarity);
+                }
+                BuckId::Buck2 => {
+                    reg.set_buck_2_pwmgpisel(gpio);
+                    reg.set_buck_2_pwmgpiinv(polarity);
+                }
+            })
+            .await?;
+        self.npm1300
+            .claim_gpio(gpio, GpioFunction::BuckForcedPwm(id))?;
+
+        if forced_pwm {
+            self.enable_forced_pwm_mode().await?;
+        } else {
+            self.disable_forced_pwm_mode().await?;
+        }
+        self.configure_operating_mode(operating_mode).await
+    }
+
+    /// Get the effective regulator mode
+    ///
+    /// A forced PWM GPIO takes precedence, then a forced PWM mode reported in the BUCK
+    /// status, then the auto/PFM selection.
+    ///
+    /// # Returns
+    /// * `Ok(BuckMode)` - The effective regulator mode
+    /// * `Err(NPM1300Error)` - An error occurred while reading the BUCK registers
+    pub async fn get_mode(&mut self) -> Result<BuckMode, crate::NPM1300Error<I2c::Error>> {
+        let pwm_control = self
+            .npm1300
+            .device
+            .buck()
+            .buckpwmctrl()
+            .read_async()
+            .await?;
+        let (gpio, polarity) = match self.id {
+            BuckId::Buck1 => (
+                pwm_control.buck_1_pwmgpisel(),
+                pwm_control.buck_1_pwmgpiinv(),
+            ),
+            BuckId::Buck2 => (
+                pwm_control.buck_2_pwmgpisel(),
+                pwm_control.buck_2_pwmgpiinv(),
+            ),
+        };
+        // GPIO select codes above GPIO4 do not select a GPIO
+        let gpio = gpio.unwrap_or(Gpio::None);
+        let polarity = polarity.unwrap_or(GpioPolarity::NotInverted);
         if gpio != Gpio::None {
-            // Configure GPIO mode as input
-            self.device
-                .gpios()
-                .gpiomode(gpio_to_register_index(gpio))
-                .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
-                .await?;
+            return Ok(BuckMode::GpioControlledPwm(gpio, polarity));
+        }
 
-            // Configure GPIO and its polarity
-            self.device
-                .buck()
-                .buckpwmctrl()
-                .modify_async(|reg| match buck_index {
-                    1 => {
-                        reg.set_buck_1_pwmgpisel(gpio);
-                        reg.set_buck_1_pwmgpiinv(polarity);
-                    }
-                    2 => {
-                        reg.set_buck_2_pwmgpisel(gpio);
-                        reg.set_buck_2_pwmgpiinv(polarity);
-                    }
-                    _ => panic!("Invalid BUCK index"),
-                })
-                .await?;
+        if self.get_status().await?.mode == RailMode::ForcedPwm {
+            return Ok(BuckMode::ForcedPwm);
         }
-        Ok(())
+
+        let control = self.npm1300.device.buck().buckctrl_0().read_async().await?;
+        let is_pfm = match self.id {
+            BuckId::Buck1 => control.buck_1_autoctrlsel() == Buck1Autoctrlsel::Pfm,
+            BuckId::Buck2 => control.buck_2_autoctrlsel() == Buck2Autoctrlsel::Pfm,
+        };
+        Ok(if is_pfm {
+            BuckMode::Pfm
+        } else {
+            BuckMode::Auto
+        })
     }
 
-    /// Configure BUCK1 GPIO forced PWM mode control
+    /// Enable or disable the pull down
     ///
     /// # Arguments
-    /// * `gpio` - GPIO to enable
-    /// * `polarity` - Polarity of GPIO
-    pub async fn set_buck1_gpio_forced_pwm_mode_control(
-        &mut self,
-        gpio: Gpio,
-        polarity: GpioPolarity,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_forced_pwm_mode_control(1, gpio, polarity)
+    /// * `enable` - true to enable the pull down, false to disable it
+    async fn set_pull_down(&mut self, enable: bool) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let id = self.id;
+        self.npm1300
+            .device
+            .buck()
+            .buckctrl_0()
+            .modify_async(|reg| match (id, enable) {
+                (BuckId::Buck1, true) => reg.set_buck_1_enpulldown(Buck1Enpulldown::High),
+                (BuckId::Buck1, false) => reg.set_buck_1_enpulldown(Buck1Enpulldown::Low),
+                (BuckId::Buck2, true) => reg.set_buck_2_enpulldown(Buck2Enpulldown::High),
+                (BuckId::Buck2, false) => reg.set_buck_2_enpulldown(Buck2Enpulldown::Low),
+            })
             .await
     }
 
-    /// Configure BUCK2 GPIO forced PWM mode control
+    /// Enable pull down
+    pub async fn enable_pull_down(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.set_pull_down(true).await
+    }
+
+    /// Disable pull down
+    pub async fn disable_pull_down(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.set_pull_down(false).await
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Get a handle to a BUCK regulator
     ///
     /// # Arguments
-    /// * `gpio` - GPIO to enable
-    /// * `polarity` - Polarity of GPIO
-    pub async fn set_buck2_gpio_forced_pwm_mode_control(
-        &mut self,
-        gpio: Gpio,
-        polarity: GpioPolarity,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_forced_pwm_mode_control(2, gpio, polarity)
-            .await
+    ///
+    /// * `id` - The BUCK regulator to control. See [`BuckId`] for available values.
+    pub fn buck(&mut self, id: BuckId) -> Buck<'_, I2c, Delay> {
+        Buck { npm1300: self, id }
     }
 
-    /// Get BUCK1 VOUT status
+    /// Set a BUCK regulator normal mode output voltage in millivolts
     ///
-    /// Note: the current voltage output setting can be read, but it is not measured by an ADC.
-    ///
-    /// # Returns
-    /// * `Ok(Buckvoutstatus)` - The current BUCK1 VOUT status
-    /// * `Err(NPM1300Error)` - An error occurred while reading the BUCK1 VOUT status
-    pub async fn get_buck1_vout_status(
+    /// See [`Buck::set_voltage_mv`].
+    pub async fn set_buck_voltage_mv(
         &mut self,
-    ) -> Result<Buckvoutstatus, crate::NPM1300Error<I2c::Error>> {
-        self.device.buck().buckvoutstatus(0).read_async().await
+        id: BuckId,
+        millivolts: u16,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.buck(id).set_voltage_mv(millivolts).await
     }
 
-    /// Get BUCK2 VOUT status
+    /// Get a BUCK regulator output voltage in millivolts
     ///
-    /// Note: the current voltage output setting can be read, but it is not measured by an ADC.
-    ///
-    /// # Returns
-    /// * `Ok(Buckvoutstatus)` - The current BUCK2 VOUT status
-    /// * `Err(NPM1300Error)` - An error occurred while reading the BUCK2 VOUT status
-    pub async fn get_buck2_vout_status(
+    /// See [`Buck::get_voltage_mv`].
+    pub async fn get_buck_voltage_mv(
         &mut self,
-    ) -> Result<Buckvoutstatus, crate::NPM1300Error<I2c::Error>> {
-        self.device.buck().buckvoutstatus(1).read_async().await
+        id: BuckId,
+    ) -> Result<u16, crate::NPM1300Error<I2c::Error>> {
+        self.buck(id).get_voltage_mv().await
     }
 
-    /// Configure BUCK1 operating mode
+    /// Check whether a BUCK regulator reports power-good
     ///
     /// # Arguments
-    /// * `mode` - The operating mode to set for BUCK1 see [`Buck1Autoctrlsel`] for available values.
-    pub async fn configure_buck1_operating_mode(
+    ///
+    /// * `id` - The BUCK regulator to check
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - `true` if the regulator is powered and its output is good
+    /// * `Err(NPM1300Error)` - An error occurred while reading the BUCK status
+    pub async fn is_buck_power_good(
         &mut self,
-        mode: Buck1Autoctrlsel,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .buck()
-            .buckctrl_0()
-            .modify_async(|reg| reg.set_buck_1_autoctrlsel(mode))
-            .await
+        id: BuckId,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        let status = self.device.buck().buckstatus().read_async().await?;
+        Ok(match id {
+            BuckId::Buck1 => status.buck_1_pwrgood() == Buck1Pwrgood::Buckpowered,
+            BuckId::Buck2 => status.buck_2_pwrgood() == Buck2Pwrgood::Buckpowered,
+        })
     }
 
-    /// Configure BUCK1 operating mode
+    /// Wait until a BUCK regulator reports power-good
+    ///
+    /// Polls the BUCK status every millisecond using the driver delay.
     ///
     /// # Arguments
-    /// * `mode` - The operating mode to set for BUCK2 see [`Buck2Autoctrlsel`] for available values.
-    pub async fn configure_buck2_operating_mode(
+    ///
+    /// * `id` - The BUCK regulator to wait for
+    /// * `timeout_ms` - The maximum time to wait in milliseconds
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::BuckPowerGoodTimeout` if power-good is not reported within the timeout
+    pub async fn wait_for_buck_power_good(
         &mut self,
-        mode: Buck2Autoctrlsel,
+        id: BuckId,
+        timeout_ms: u32,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .buck()
-            .buckctrl_0()
-            .modify_async(|reg| reg.set_buck_2_autoctrlsel(mode))
-            .await
-    }
-
-    /// Enable BUCK1 pull down
-    pub async fn enable_buck1_pull_down(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .buck()
-            .buckctrl_0()
-            .modify_async(|reg| reg.set_buck_1_enpulldown(Buck1Enpulldown::High))
-            .await
-    }
-
-    /// Disable BUCK1 pull down
-    pub async fn disable_buck1_pull_down(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .buck()
-            .buckctrl_0()
-            .modify_async(|reg| reg.set_buck_1_enpulldown(Buck1Enpulldown::Low))
-            .await
-    }
-
-    /// Enable BUCK2 pull down
-    pub async fn enable_buck2_pull_down(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .buck()
-            .buckctrl_0()
-            .modify_async(|reg| reg.set_buck_2_enpulldown(Buck2Enpulldown::High))
-            .await
+        let mut elapsed_ms = 0;
+        loop {
+            if self.is_buck_power_good(id).await? {
+                return Ok(());
+            }
+            if elapsed_ms >= timeout_ms {
+                return Err(crate::NPM1300Error::BuckPowerGoodTimeout(id));
+            }
+            self.delay.delay_ms(POWER_GOOD_POLL_INTERVAL_MS).await;
+            elapsed_ms += POWER_GOOD_POLL_INTERVAL_MS;
+        }
     }
 
-    /// Disable BUCK2 pull down
-    pub async fn disable_buck2_pull_down(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
+    /// Get a BUCK regulator VOUT status
+    async fn get_buck_vout_status(
+        &mut self,
+        id: BuckId,
+    ) -> Result<BuckVoltage, crate::NPM1300Error<I2c::Error>> {
+        let status = self
+            .device
             .buck()
-            .buckctrl_0()
-            .modify_async(|reg| reg.set_buck_2_enpulldown(Buck2Enpulldown::Low))
-            .await
+            .buckvoutstatus(id.index())
+            .read_async()
+            .await?;
+        Ok(saturating_buck_voltage(status.buckvoutstatus()))
     }
 
     /// Get BUCK status
-    pub async fn get_buck_status(&mut self) -> Result<Buckstatus, crate::NPM1300Error<I2c::Error>> {
-        self.device.buck().buckstatus().read_async().await
+    ///
+    /// # Returns
+    /// * `Ok(BuckStatus)` - The mode, power-good, PWM-ok and output voltage of both regulators
+    /// * `Err(NPM1300Error)` - An error occurred while reading the BUCK status
+    pub async fn get_buck_status(&mut self) -> Result<BuckStatus, crate::NPM1300Error<I2c::Error>> {
+        let status = self.device.buck().buckstatus().read_async().await?;
+        let buck1_voltage = self.get_buck_vout_status(BuckId::Buck1).await?;
+        let buck2_voltage = self.get_buck_vout_status(BuckId::Buck2).await?;
+
+        Ok(BuckStatus {
+            buck1: RailStatus {
+                mode: match status.buck_1_mode() {
+                    Buck1Mode::Automode => RailMode::Auto,
+                    Buck1Mode::Pfmmode => RailMode::Pfm,
+                    Buck1Mode::Pwmmode => RailMode::ForcedPwm,
+                },
+                is_power_good: status.buck_1_pwrgood() == Buck1Pwrgood::Buckpowered,
+                is_pwm_ok: status.buck_1_pwmok() == Buck1Pwmok::Pwmmodeenabled,
+                voltage: buck1_voltage,
+            },
+            buck2: RailStatus {
+                mode: match status.buck_2_mode() {
+                    Buck2Mode::Automode => RailMode::Auto,
+                    Buck2Mode::Pfmmode => RailMode::Pfm,
+                    Buck2Mode::Pwmmode => RailMode::ForcedPwm,
+                },
+                is_power_good: status.buck_2_pwrgood() == Buck2Pwrgood::Buckpowered,
+                is_pwm_ok: status.buck_2_pwmok() == Buck2Pwmok::Pwmmodeenabled,
+                voltage: buck2_voltage,
+            },
+        })
     }
 }
@@ -1,5 +1,116 @@
+use crate::gpios::{Gpio, GpioPolarity};
+
+/// BUCK regulators available on the nPM1300
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BuckId {
+    Buck1,
+    Buck2,
+}
+
+impl BuckId {
+    /// Index of the regulator in the repeated BUCK registers
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Self::Buck1 => 0,
+            Self::Buck2 => 1,
+        }
+    }
+}
+
+/// Effective BUCK regulator mode
+///
+/// Combines the auto/PFM selection, the forced PWM tasks and the forced PWM GPIO control.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BuckMode {
+    /// Auto switching between PFM and PWM
+    Auto,
+    /// PFM mode only
+    Pfm,
+    /// Forced PWM mode set by software
+    ForcedPwm,
+    /// Forced PWM mode while the GPIO is active, auto mode otherwise
+    GpioControlledPwm(Gpio, GpioPolarity),
+}
+
+/// BUCK regulator mode reported by the BUCK status
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum RailMode {
+    /// Auto switching between PFM and PWM
+    Auto,
+    /// PFM mode
+    Pfm,
+    /// Forced PWM mode
+    ForcedPwm,
+}
+
+/// Status of a single BUCK regulator
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct RailStatus {
+    /// Current regulator mode
+    pub mode: RailMode,
+    /// Indicates if the regulator is powered and its output is good
+    pub is_power_good: bool,
+    /// Indicates if the regulator is running in PWM mode
+    pub is_pwm_ok: bool,
+    /// Output voltage currently selected by the regulator
+    ///
+    /// Note: this is the output voltage setting, it is not measured by an ADC.
+    pub voltage: BuckVoltage,
+}
+
+/// Status of both BUCK regulators
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BuckStatus {
+    pub buck1: RailStatus,
+    pub buck2: RailStatus,
+}
+
+impl BuckStatus {
+    /// Get the status of a BUCK regulator
+    pub fn rail(&self, id: BuckId) -> RailStatus {
+        match id {
+            BuckId::Buck1 => self.buck1,
+            BuckId::Buck2 => self.buck2,
+        }
+    }
+}
+
+/// BUCK voltage ramp configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BuckRampConfig {
+    /// Voltage change per step in millivolts, a non-zero multiple of 100 mV
+    pub step_mv: u16,
+    /// Time to wait after each step before checking power-good in microseconds
+    pub dwell_us: u32,
+}
+
+impl Default for BuckRampConfig {
+    fn default() -> Self {
+        Self {
+            step_mv: BuckVoltage::STEP_MV,
+            dwell_us: 100,
+        }
+    }
+}
+
+/// BUCK regulator operating modes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BuckOperatingMode {
+    /// Auto switching between PFM and PWM
+    Auto,
+    /// PFM mode only
+    Pfm,
+}
+
 /// Buck regulator voltages available on the nPM1300
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum BuckVoltage {
     V1_0 = 0,
@@ -71,3 +182,82 @@ impl From<BuckVoltage> for u8 {
         voltage as u8
     }
 }
+
+impl BuckVoltage {
+    /// Lowest BUCK output voltage in millivolts
+    pub const MIN_MV: u16 = 1000;
+    /// Highest BUCK output voltage in millivolts
+    pub const MAX_MV: u16 = 3300;
+    /// BUCK output voltage step in millivolts
+    pub const STEP_MV: u16 = 100;
+
+    /// Get the closest BUCK voltage to a voltage in millivolts
+    ///
+    /// Returns `None` if the voltage is outside the 1.0-3.3 V range.
+    pub fn from_mv_rounded(millivolts: u16) -> Option<Self> {
+        if !(Self::MIN_MV..=Self::MAX_MV).contains(&millivolts) {
+            return None;
+        }
+        let step = (millivolts - Self::MIN_MV + Self::STEP_MV / 2) / Self::STEP_MV;
+        Self::try_from(step as u8).ok()
+    }
+}
+
+// Add conversion from BuckVoltage to millivolts
+impl From<BuckVoltage> for u16 {
+    fn from(voltage: BuckVoltage) -> Self {
+        match voltage {
+            // V3_30 selects the same output voltage as V3_3
+            BuckVoltage::V3_30 => BuckVoltage::MAX_MV,
+            _ => BuckVoltage::MIN_MV + voltage as u16 * BuckVoltage::STEP_MV,
+        }
+    }
+}
+
+// Add conversion from millivolts to BuckVoltage
+impl TryFrom<u16> for BuckVoltage {
+    type Error = ();
+
+    /// Fails unless the voltage is exactly one of the BUCK output voltages
+    fn try_from(millivolts: u16) -> Result<Self, Self::Error> {
+        if !millivolts.is_multiple_of(Self::STEP_MV) {
+            return Err(());
+        }
+        Self::from_mv_rounded(millivolts).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn millivolts_round_trip() {
+        for code in 0..=23u8 {
+            let voltage = BuckVoltage::try_from(code).unwrap();
+            let millivolts = u16::from(voltage);
+            assert_eq!(millivolts, 1000 + code as u16 * 100);
+            assert_eq!(BuckVoltage::try_from(millivolts), Ok(voltage));
+        }
+        assert_eq!(u16::from(BuckVoltage::V3_30), 3300);
+    }
+
+    #[test]
+    fn millivolts_are_rounded_to_the_closest_step() {
+        assert_eq!(BuckVoltage::from_mv_rounded(1000), Some(BuckVoltage::V1_0));
+        assert_eq!(BuckVoltage::from_mv_rounded(1049), Some(BuckVoltage::V1_0));
+        assert_eq!(BuckVoltage::from_mv_rounded(1050), Some(BuckVoltage::V1_1));
+        assert_eq!(BuckVoltage::from_mv_rounded(1840), Some(BuckVoltage::V1_8));
+        assert_eq!(BuckVoltage::from_mv_rounded(3300), Some(BuckVoltage::V3_3));
+    }
+
+    #[test]
+    fn out_of_range_millivolts_are_rejected() {
+        assert_eq!(BuckVoltage::from_mv_rounded(999), None);
+        assert_eq!(BuckVoltage::from_mv_rounded(3301), None);
+        assert_eq!(BuckVoltage::try_from(900u16), Err(()));
+        assert_eq!(BuckVoltage::try_from(3400u16), Err(()));
+        assert_eq!(BuckVoltage::try_from(1850u16), Err(()));
+        assert_eq!(BuckVoltage::try_from(25u8), Err(()));
+    }
+}
@@ -1,12 +1,53 @@
+mod power_budget;
 mod types;
 
 // Re-export everything in types.rs
+pub use power_budget::*;
 pub use types::*;
 
 use libm::roundf;
 
 use crate::{common::Task, Bchgilimbatactive, Dietemphigh};
 
+/// Compute the 10-bit NTC threshold for a thermistor resistance
+///
+/// # Arguments
+///
+/// * `desired_resistance` - The NTC resistance at the desired threshold temperature
+/// * `reference_resistance_25c` - The NTC resistance at 25 degrees Celsius
+///
+/// # Returns
+///
+/// The threshold, or `None` if it is outside the 10-bit allowable range
+pub(crate) fn ntc_threshold_code(
+    desired_resistance: u32,
+    reference_resistance_25c: u32,
+) -> Option<u16> {
+    let threshold = roundf(
+        1024.0 * (desired_resistance as f32)
+            / (desired_resistance as f32 + reference_resistance_25c as f32),
+    );
+
+    // Ensure the threshold fits within a 10-bit range
+    if !(0.0..=1023.0).contains(&threshold) {
+        return None;
+    }
+
+    // Convert the threshold to a 10-bit unsigned integer
+    Some(threshold as u16)
+}
+
+/// Convert a die temperature in degrees Celsius to the 10-bit code used by the
+/// die temperature result and threshold registers
+pub(crate) fn die_temperature_code(temperature_celsius: f32) -> f32 {
+    roundf((394.67 - temperature_celsius) / 0.7926)
+}
+
+/// Convert a 10-bit die temperature code to degrees Celsius
+pub(crate) fn die_temperature_celsius(code: u16) -> f32 {
+    394.67 - 0.7926 * code as f32
+}
+
 impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
     crate::NPM1300<I2c, Delay>
 {
@@ -272,6 +313,10 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .write_async(|reg| reg.set_bchgisetchargelsb(lsb))
             .await?;
 
+        // Remember the charge current actually written for IBAT conversions, the LSB register
+        // has a 2 mA resolution
+        self.charge_current_ma = Some(current_ma & !1);
+
         // Only re-enable if it was enabled before
         if was_enabled {
             self.enable_battery_charging().await
@@ -328,7 +373,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .charger()
             .bchgisetdischargelsb()
             .write_async(|reg| reg.set_bchgisetdischargelsb(lsb))
-            .await
+            .await?;
+
+        // Remember the discharge current limit for IBAT conversions
+        self.discharge_current_limit = Some(limit);
+        Ok(())
     }
 
     /// Set the battery charging termination voltage for normal temperature conditions
@@ -427,18 +476,8 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         reference_resistance_25c: u32,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         // Calculate the 10-bit threshold
-        let threshold = roundf(
-            1024.0 * (desired_resistance as f32)
-                / (desired_resistance as f32 + reference_resistance_25c as f32),
-        );
-
-        // Ensure the threshold fits within a 10-bit range
-        if !(0.0..=1023.0).contains(&threshold) {
-            return Err(crate::NPM1300Error::InvalidNtcThreshold);
-        }
-
-        // Convert the threshold to a 10-bit unsigned integer
-        let threshold = threshold as u16;
+        let threshold = ntc_threshold_code(desired_resistance, reference_resistance_25c)
+            .ok_or(crate::NPM1300Error::InvalidNtcThreshold)?;
 
         // Extract MSB (upper 8 bits) and LSB (lower 2 bits)
         let msb = (threshold >> 2) as u8;
@@ -610,7 +649,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         }
 
         // Calculate the 10-bit threshold
-        let k_die_temp = roundf((394.67 - temperature_celsius as f32) / 0.7926);
+        let k_die_temp = die_temperature_code(temperature_celsius as f32);
 
         // Convert the threshold to a 10-bit unsigned integer
         let k_die_temp = k_die_temp as u16;
@@ -648,6 +687,92 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         }
     }
 
+    /// Get the die temperature threshold for the charger device
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold_type` - The type of threshold to get (Stop or Resume)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f32)` - The threshold in degrees Celsius
+    /// * `Err(NPM1300Error)` - An error occurred while reading the threshold registers
+    pub async fn get_die_temperature_threshold(
+        &mut self,
+        threshold_type: DieTemperatureThresholdType,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        let (msb, lsb) = match threshold_type {
+            DieTemperatureThresholdType::Stop => (
+                self.device
+                    .charger()
+                    .dietempstop()
+                    .read_async()
+                    .await?
+                    .dietempstopchg(),
+                self.device
+                    .charger()
+                    .dietempstoplsb()
+                    .read_async()
+                    .await?
+                    .dietempstopchglsb(),
+            ),
+            DieTemperatureThresholdType::Resume => (
+                self.device
+                    .charger()
+                    .dietempresume()
+                    .read_async()
+                    .await?
+                    .dietempresumechg(),
+                self.device
+                    .charger()
+                    .dietempresumelsb()
+                    .read_async()
+                    .await?
+                    .dietempresumechglsb(),
+            ),
+        };
+
+        Ok(die_temperature_celsius((msb as u16) << 2 | (lsb as u16)))
+    }
+
+    /// Get the die temperature thermal status
+    ///
+    /// Measures the die temperature and reports it together with the stop and resume
+    /// thresholds and the die temperature comparator status.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DieTemperatureStatus)` - The die temperature, thresholds and hysteresis state
+    /// * `Err(NPM1300Error)` - An error occurred while measuring or reading the thresholds
+    pub async fn get_die_temperature_status(
+        &mut self,
+    ) -> Result<DieTemperatureStatus, crate::NPM1300Error<I2c::Error>> {
+        let temperature_celsius = self.measure_die_temperature().await?;
+        let stop_threshold_celsius = self
+            .get_die_temperature_threshold(DieTemperatureThresholdType::Stop)
+            .await?;
+        let resume_threshold_celsius = self
+            .get_die_temperature_threshold(DieTemperatureThresholdType::Resume)
+            .await?;
+        let is_above_stop_threshold = self.is_die_temperature_above_charging_threshold().await?;
+
+        let state = if is_above_stop_threshold {
+            DieTemperatureState::Throttled
+        } else if temperature_celsius >= resume_threshold_celsius {
+            DieTemperatureState::Hysteresis
+        } else {
+            DieTemperatureState::Normal
+        };
+
+        Ok(DieTemperatureStatus {
+            temperature_celsius,
+            stop_threshold_celsius,
+            resume_threshold_celsius,
+            is_above_stop_threshold,
+            state,
+        })
+    }
+
     /// Get the discharge current limit active status
     ///
     /// Returns true if discharge current limit is active, false if not
@@ -0,0 +1,210 @@
+/// Lowest charge current supported by the charger in milliamps (mA)
+const MIN_CHARGE_CURRENT_MA: u16 = 32;
+/// Highest charge current supported by the charger in milliamps (mA)
+const MAX_CHARGE_CURRENT_MA: u16 = 800;
+
+/// Power budget controller configuration
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PowerBudgetConfig {
+    /// Lowest charge current the controller may set in milliamps (mA), at least 32 mA
+    pub min_charge_current_ma: u16,
+    /// Highest charge current the controller may set in milliamps (mA), at most 800 mA
+    pub max_charge_current_ma: u16,
+    /// Charge current increment used while ramping up in milliamps (mA)
+    pub step_ma: u16,
+    /// Input current kept in reserve for the system load in milliamps (mA)
+    pub hysteresis_ma: u16,
+    /// VSYS voltage below which the system is considered overloaded in volts
+    pub min_vsys_v: f32,
+}
+
+/// Result of a [`PowerBudgetController::update`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PowerBudgetUpdate {
+    /// Charge current in milliamps (mA) after the update
+    pub charge_current_ma: u16,
+    /// Indicates if the charge current was changed by this update
+    pub is_changed: bool,
+    /// Indicates if the input was overloaded, i.e. the VBUS current limit was active, VSYS was
+    /// below [`PowerBudgetConfig::min_vsys_v`] or the battery was supplementing the input
+    pub is_overloaded: bool,
+    /// VBUS input current limit in milliamps (mA)
+    pub vbus_current_limit_ma: u16,
+    /// Measured VSYS voltage in volts
+    pub vsys_v: f32,
+    /// Measured battery current in microamps (µA), negative while charging, `None` if the PMIC
+    /// flagged the measurement as invalid
+    pub ibat_ua: Option<i32>,
+}
+
+/// Input power budget controller
+///
+/// Keeps the system load served first when running from VBUS by lowering the charge current
+/// as soon as the VBUS current limit is hit or the battery starts supplementing the input.
+/// The charge current is then ramped back up one step per update while the input has more
+/// than `hysteresis_ma` of spare current.
+///
+/// Battery current is measured by triggering a VBAT measurement with auto IBAT measurement
+/// enabled, so the discharge current limit must have been set through
+/// [`NPM1300::set_discharge_current_limit`](crate::NPM1300::set_discharge_current_limit).
+pub struct PowerBudgetController {
+    config: PowerBudgetConfig,
+    charge_current_ma: u16,
+    /// Estimated system load on the VBUS input in milliamps (mA)
+    load_estimate_ma: u16,
+}
+
+impl PowerBudgetController {
+    /// Create a new power budget controller
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The controller configuration
+    pub fn new(config: PowerBudgetConfig) -> Self {
+        Self {
+            config,
+            charge_current_ma: config.max_charge_current_ma,
+            load_estimate_ma: 0,
+        }
+    }
+
+    /// Get the charge current in milliamps (mA) last set by the controller
+    pub fn charge_current_ma(&self) -> u16 {
+        self.charge_current_ma
+    }
+
+    /// Get the estimated system load on the VBUS input in milliamps (mA)
+    pub fn load_estimate_ma(&self) -> u16 {
+        self.load_estimate_ma
+    }
+
+    /// Start the controller
+    ///
+    /// Validates the configuration, enables auto IBAT measurement and sets the charge current to
+    /// the configured maximum.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidPowerBudgetConfig` if the charge current range is outside
+    /// 32..=800 mA, the minimum is above the maximum, or `step_ma` is zero.
+    pub async fn start<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let config = &self.config;
+        if config.min_charge_current_ma < MIN_CHARGE_CURRENT_MA
+            || config.max_charge_current_ma > MAX_CHARGE_CURRENT_MA
+            || config.min_charge_current_ma > config.max_charge_current_ma
+            || config.step_ma == 0
+        {
+            return Err(crate::NPM1300Error::InvalidPowerBudgetConfig);
+        }
+
+        self.charge_current_ma = even_ma(config.max_charge_current_ma);
+        self.load_estimate_ma = 0;
+        npm1300.configure_auto_ibat_measurement(true).await?;
+        npm1300.set_charger_current(self.charge_current_ma).await
+    }
+
+    /// Update the charge current from the measured input budget
+    ///
+    /// While VBUS is absent or the IBAT measurement is invalid the charge current is left
+    /// unchanged.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PowerBudgetUpdate)` - The measurements and resulting charge current
+    /// * `Err(NPM1300Error::IbatCurrentLimitsNotConfigured)` - The discharge current limit has
+    ///   not been set through this driver
+    /// * `Err(NPM1300Error)` - An error occurred while measuring or setting the charge current
+    pub async fn update<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<PowerBudgetUpdate, crate::NPM1300Error<I2c::Error>> {
+        let Some(discharge_current_limit) = npm1300.discharge_current_limit else {
+            return Err(crate::NPM1300Error::IbatCurrentLimitsNotConfigured);
+        };
+
+        let status = npm1300.get_vbus_in_status().await?;
+        let vbus_current_limit_ma = npm1300
+            .get_vbus_in_current_limit()
+            .await?
+            .current_limit_ma();
+        let vsys_v = npm1300.measure_vsys().await?;
+        let ibat_ua = npm1300
+            .measure_ibat(discharge_current_limit, self.charge_current_ma)
+            .await?;
+
+        let mut update = PowerBudgetUpdate {
+            charge_current_ma: self.charge_current_ma,
+            is_changed: false,
+            is_overloaded: false,
+            vbus_current_limit_ma,
+            vsys_v,
+            ibat_ua,
+        };
+        let Some(ibat_ua) = ibat_ua else {
+            return Ok(update);
+        };
+        if !status.is_vbus_in_present {
+            return Ok(update);
+        }
+
+        // Current flowing into the battery, zero while discharging
+        let charge_ma = (ibat_ua.min(0).unsigned_abs() / 1000).min(u16::MAX as u32) as u16;
+        let headroom_ma = vbus_current_limit_ma.saturating_sub(charge_ma);
+        update.is_overloaded = status.is_vbus_in_current_limit_active
+            || vsys_v < self.config.min_vsys_v
+            || ibat_ua > 0;
+
+        let target_ma = if update.is_overloaded {
+            // The whole input is in use, so the system takes everything the battery does not
+            self.load_estimate_ma = headroom_ma;
+            let budget_ma = vbus_current_limit_ma
+                .saturating_sub(self.load_estimate_ma)
+                .saturating_sub(self.config.hysteresis_ma);
+            budget_ma.min(self.charge_current_ma.saturating_sub(self.config.step_ma))
+        } else {
+            // The load is at most the spare input current, decay the estimate to probe upwards
+            self.load_estimate_ma = self
+                .load_estimate_ma
+                .saturating_sub(self.config.step_ma)
+                .min(headroom_ma);
+            let next_ma = self.charge_current_ma.saturating_add(self.config.step_ma);
+            if next_ma
+                .saturating_add(self.load_estimate_ma)
+                .saturating_add(self.config.hysteresis_ma)
+                <= vbus_current_limit_ma
+            {
+                next_ma
+            } else {
+                self.charge_current_ma
+            }
+        };
+        let target_ma = even_ma(target_ma.clamp(
+            self.config.min_charge_current_ma,
+            self.config.max_charge_current_ma,
+        ));
+
+        if target_ma != self.charge_current_ma {
+            npm1300.set_charger_current(target_ma).await?;
+            self.charge_current_ma = target_ma;
+            update.charge_current_ma = target_ma;
+            update.is_changed = true;
+        }
+        Ok(update)
+    }
+}
+
+/// Round a charge current down to the 2 mA charger resolution
+fn even_ma(current_ma: u16) -> u16 {
+    current_ma & !1
+}
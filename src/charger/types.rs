@@ -505,6 +505,7 @@ impl From<ChargerConfigDisableChargeWarm> for u8 {
 }
 
 /// Discharge current limit settings
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum DischargeCurrentLimit {
     Low,
@@ -512,7 +513,7 @@ pub enum DischargeCurrentLimit {
 }
 
 /// Temperature threshold regions for NTC measurements
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum NtcThresholdRegion {
     /// Cold temperature threshold (lowest)
@@ -535,6 +536,35 @@ pub enum DieTemperatureThresholdType {
     Resume,
 }
 
+/// Die temperature hysteresis state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum DieTemperatureState {
+    /// Die temperature is below the resume threshold
+    Normal,
+    /// Die temperature is between the resume and stop thresholds, charging is not stopped
+    Hysteresis,
+    /// Die temperature exceeded the stop threshold, charging is stopped until the die
+    /// temperature falls below the resume threshold
+    Throttled,
+}
+
+/// Die temperature thermal status
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct DieTemperatureStatus {
+    /// Measured die temperature in degrees Celsius
+    pub temperature_celsius: f32,
+    /// Die temperature at which charging is stopped in degrees Celsius
+    pub stop_threshold_celsius: f32,
+    /// Die temperature at which charging is resumed in degrees Celsius
+    pub resume_threshold_celsius: f32,
+    /// Die temperature comparator status (DIETEMPSTATUS)
+    pub is_above_stop_threshold: bool,
+    /// Hysteresis state derived from the comparator status and the measured temperature
+    pub state: DieTemperatureState,
+}
+
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct ChargerStatus {
     /// Indicates if a battery is physically connected to the system
@@ -27,3 +27,13 @@ impl From<Task> for u8 {
         task as u8
     }
 }
+
+/// Monotonic time source injected by the application
+///
+/// Used by the driver helpers that need timestamps rather than delays.
+pub trait Clock {
+    /// Current time in milliseconds
+    ///
+    /// The value must never decrease; its origin is irrelevant.
+    fn now_ms(&self) -> u64;
+}
@@ -1,5 +1,7 @@
+mod pin;
 mod types;
 
+pub use pin::*;
 // Re-export everything in types.rs
 pub use types::*;
 
@@ -11,7 +13,8 @@ pub struct GpioConfigBuilder {
 /// GPIO Configuration structure
 ///
 /// Pull-down is prioritized if both pull-up and pull-down are activated on a GPIO pin at the same time.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct GpioConfig {
     mode: GpioMode,
     drive_strength: GpioDriveStrength,
@@ -34,6 +37,32 @@ impl Default for GpioConfig {
     }
 }
 
+impl GpioConfig {
+    pub fn mode(&self) -> GpioMode {
+        self.mode
+    }
+
+    pub fn drive_strength(&self) -> GpioDriveStrength {
+        self.drive_strength
+    }
+
+    pub fn pull_up(&self) -> GpioPullUp {
+        self.pull_up
+    }
+
+    pub fn pull_down(&self) -> GpioPullDown {
+        self.pull_down
+    }
+
+    pub fn open_drain(&self) -> GpioOpenDrain {
+        self.open_drain
+    }
+
+    pub fn debounce(&self) -> GpioDebounce {
+        self.debounce
+    }
+}
+
 impl GpioConfigBuilder {
     pub fn new() -> Self {
         Self {
@@ -85,14 +114,105 @@ impl Default for GpioConfigBuilder {
 impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
     crate::NPM1300<I2c, Delay>
 {
+    /// Get the function a GPIO is assigned to
+    ///
+    /// Only assignments made through this driver instance are tracked.
+    ///
+    /// # Returns
+    ///
+    /// The function owning the GPIO, or `None` if the GPIO is free or is [`Gpio::None`]
+    pub fn get_gpio_function(&self, gpio: Gpio) -> Option<GpioFunction> {
+        gpio.index().and_then(|index| self.gpio_functions[index])
+    }
+
+    /// Get the functions all GPIOs are assigned to, indexed by GPIO number
+    ///
+    /// Only assignments made through this driver instance are tracked.
+    pub fn get_gpio_functions(&self) -> [Option<GpioFunction>; GPIO_COUNT] {
+        self.gpio_functions
+    }
+
+    /// Release a GPIO so it can be assigned to another function
+    ///
+    /// This only updates the driver's assignment tracking, the GPIO configuration and the
+    /// function using it are left untouched.
+    pub fn release_gpio(&mut self, gpio: Gpio) {
+        if let Some(index) = gpio.index() {
+            self.gpio_functions[index] = None;
+        }
+    }
+
+    /// Assign a GPIO to a function
+    ///
+    /// Any other GPIO previously assigned to the same function is released, so that
+    /// reassigning a function moves it to the new GPIO. Passing [`Gpio::None`] only releases
+    /// the function.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function
+    pub(crate) fn claim_gpio(
+        &mut self,
+        gpio: Gpio,
+        function: GpioFunction,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.check_gpio_available(gpio, function)?;
+        self.release_gpio_function(function);
+        if let Some(index) = gpio.index() {
+            self.gpio_functions[index] = Some(function);
+        }
+        Ok(())
+    }
+
+    /// Check that a GPIO can be assigned to a function without assigning it
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function
+    pub(crate) fn check_gpio_available(
+        &self,
+        gpio: Gpio,
+        function: GpioFunction,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if let Some(index) = gpio.index() {
+            if self.gpio_functions[index].is_some_and(|owner| owner != function) {
+                return Err(crate::NPM1300Error::GpioAlreadyInUse(gpio));
+            }
+        }
+        Ok(())
+    }
+
+    /// Release the GPIO assigned to a function, if any
+    pub(crate) fn release_gpio_function(&mut self, function: GpioFunction) {
+        for owner in self.gpio_functions.iter_mut() {
+            if *owner == Some(function) {
+                *owner = None;
+            }
+        }
+    }
+
+    /// Configure a GPIO
+    ///
+    /// # Arguments
+    ///
+    /// * `gpio` - The GPIO to configure
+    /// * `config` - The GPIO configuration, see [`GpioConfigBuilder`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidGpio` if `gpio` is [`Gpio::None`], or
+    /// `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function. Call
+    /// [`release_gpio`](Self::release_gpio) first to reconfigure a GPIO used by the driver.
     pub async fn configure_gpio(
         &mut self,
-        pin: usize,
+        gpio: Gpio,
         config: GpioConfig,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        if pin > 4 {
-            panic!("GPIO pin number must be between 0 and 4");
-        }
+        let pin = gpio.index().ok_or(crate::NPM1300Error::InvalidGpio)?;
+        let function = GpioFunction::from(config.mode);
+        self.check_gpio_available(gpio, function)?;
+        self.disarm_pending_gpio_events().await?;
+
         // GPIO mode configuration
         self.device
             .gpios()
@@ -134,30 +254,85 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .gpiodebounce(pin)
             .write_async(|reg| reg.set_gpiodebounce(config.debounce))
             .await?;
+
+        // Only track the GPIO once it is configured
+        self.gpio_functions[pin] = Some(function);
         Ok(())
     }
 
+    /// Get GPIO configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `gpio` - The GPIO to read
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidGpio` if `gpio` is [`Gpio::None`], or
+    /// `NPM1300Error::InvalidGpioMode` if the GPIOMODE register holds a reserved value
+    pub async fn get_gpio_config(
+        &mut self,
+        gpio: Gpio,
+    ) -> Result<GpioConfig, crate::NPM1300Error<I2c::Error>> {
+        let pin = gpio.index().ok_or(crate::NPM1300Error::InvalidGpio)?;
+
+        let mode = self.device.gpios().gpiomode(pin).read_async().await?;
+        let drive = self.device.gpios().gpiodrive(pin).read_async().await?;
+        let pull_up = self.device.gpios().gpiopuen(pin).read_async().await?;
+        let pull_down = self.device.gpios().gpiopden(pin).read_async().await?;
+        let open_drain = self.device.gpios().gpioopendrain(pin).read_async().await?;
+        let debounce = self.device.gpios().gpiodebounce(pin).read_async().await?;
+
+        // GPIOMODE is 4 bits wide but only values 0..=9 are defined. The other fields are a
+        // single bit with both values defined, so their conversions cannot fail.
+        Ok(GpioConfig {
+            mode: mode
+                .gpiomode()
+                .map_err(|_| crate::NPM1300Error::InvalidGpioMode(gpio))?,
+            drive_strength: drive.gpiodrive().unwrap(),
+            pull_up: pull_up.gpiopuen().unwrap(),
+            pull_down: pull_down.gpiopden().unwrap(),
+            open_drain: open_drain.gpioopendrain().unwrap(),
+            debounce: debounce.gpiodebounce().unwrap(),
+        })
+    }
+
+    /// Get the configuration of all GPIOs, indexed by GPIO number
+    pub async fn get_all_gpio_configs(
+        &mut self,
+    ) -> Result<[GpioConfig; GPIO_COUNT], crate::NPM1300Error<I2c::Error>> {
+        let mut configs: [GpioConfig; GPIO_COUNT] = Default::default();
+        for (config, gpio) in configs.iter_mut().zip(Gpio::ALL) {
+            *config = self.get_gpio_config(gpio).await?;
+        }
+        Ok(configs)
+    }
+
     /// Get GPIO status
     ///
     /// # Arguments
     ///
-    /// * `pin` - GPIO pin number (0-4)
+    /// * `gpio` - The GPIO to read
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidGpio` if `gpio` is [`Gpio::None`]
     pub async fn get_gpio_status(
         &mut self,
-        pin: usize,
+        gpio: Gpio,
     ) -> Result<GpioStatus, crate::NPM1300Error<I2c::Error>> {
-        if pin > 4 {
-            panic!("GPIO pin number must be between 0 and 4");
+        if gpio == Gpio::None {
+            return Err(crate::NPM1300Error::InvalidGpio);
         }
         let status = self.device.gpios().gpiostatus().read_async().await?;
 
-        Ok(match pin {
-            0 => status.gpio_0_status().unwrap(),
-            1 => status.gpio_1_status().unwrap(),
-            2 => status.gpio_2_status().unwrap(),
-            3 => status.gpio_3_status().unwrap(),
-            4 => status.gpio_4_status().unwrap(),
-            _ => unreachable!(), // We already checked pin range above
+        Ok(match gpio {
+            Gpio::Gpio0 => status.gpio_0_status().unwrap(),
+            Gpio::Gpio1 => status.gpio_1_status().unwrap(),
+            Gpio::Gpio2 => status.gpio_2_status().unwrap(),
+            Gpio::Gpio3 => status.gpio_3_status().unwrap(),
+            Gpio::Gpio4 => status.gpio_4_status().unwrap(),
+            Gpio::None => unreachable!(), // We already checked for no GPIO above
         })
     }
 }
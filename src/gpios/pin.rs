@@ -0,0 +1,531 @@
+use core::cell::{RefCell, RefMut};
+use core::future::Future;
+use core::task::{Context, Poll, Waker};
+
+use embedded_hal::digital::{ErrorKind, ErrorType, InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal_async::digital::Wait;
+
+use super::{Gpio, GpioConfigBuilder, GpioMode, GpioPullDown, GpioStatus};
+
+/// Drive a future to completion by polling it in a loop
+///
+/// Used by the blocking `embedded_hal::digital` impls, which have no executor to wait on. The
+/// I2C and delay implementations must make progress when polled without being woken, as
+/// interrupt or timer driven implementations do.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+impl<I2cError: core::fmt::Debug> embedded_hal::digital::Error for crate::NPM1300Error<I2cError> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// Borrow the shared driver for a single operation
+///
+/// Pins borrow the driver only while they access the PMIC, never while waiting on the
+/// interrupt line, so other pins and direct driver calls can run between pin operations.
+fn borrow_driver<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>(
+    npm1300: &RefCell<crate::NPM1300<I2c, Delay>>,
+) -> Result<RefMut<'_, crate::NPM1300<I2c, Delay>>, crate::NPM1300Error<I2c::Error>> {
+    npm1300
+        .try_borrow_mut()
+        .map_err(|_| crate::NPM1300Error::DriverBusy)
+}
+
+/// PMIC GPIO used as a digital output
+///
+/// The level is set by switching the GPIO between the GPO Logic0 and Logic1 modes. The
+/// blocking [`OutputPin`] and [`StatefulOutputPin`] traits are implemented for drivers that
+/// are generic over HAL pins. They busy-wait on the I2C transfer, so prefer the async
+/// [`set_high`](Self::set_high) and [`set_low`](Self::set_low) otherwise.
+///
+/// The output shares the driver through a [`RefCell`], in the style of `embedded-hal-bus`, so
+/// several pins and direct driver calls can be used side by side. The driver is borrowed for
+/// each operation only, which fails with `NPM1300Error::DriverBusy` if it is already borrowed,
+/// e.g. by another task in the middle of an I2C transfer.
+pub struct GpioOutput<
+    'a,
+    I2c: embedded_hal_async::i2c::I2c,
+    Delay: embedded_hal_async::delay::DelayNs,
+> {
+    npm1300: &'a RefCell<crate::NPM1300<I2c, Delay>>,
+    gpio: Gpio,
+    is_high: bool,
+}
+
+impl<'a, I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    GpioOutput<'a, I2c, Delay>
+{
+    /// Use a GPIO as a digital output
+    ///
+    /// # Arguments
+    ///
+    /// * `npm1300` - The shared driver
+    /// * `gpio` - The GPIO to use
+    /// * `is_high` - The initial output level
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidGpio` if `gpio` is [`Gpio::None`],
+    /// `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function, or
+    /// `NPM1300Error::DriverBusy` if the driver is already borrowed
+    #[allow(clippy::await_holding_refcell_ref)] // Concurrent access fails with DriverBusy
+    pub async fn new(
+        npm1300: &'a RefCell<crate::NPM1300<I2c, Delay>>,
+        gpio: Gpio,
+        is_high: bool,
+    ) -> Result<Self, crate::NPM1300Error<I2c::Error>> {
+        let config = GpioConfigBuilder::new()
+            .mode(output_mode(is_high))
+            .pull_down(GpioPullDown::Disable)
+            .build();
+        borrow_driver(npm1300)?.configure_gpio(gpio, config).await?;
+
+        Ok(Self {
+            npm1300,
+            gpio,
+            is_high,
+        })
+    }
+
+    /// Get the GPIO driven by this output
+    pub fn gpio(&self) -> Gpio {
+        self.gpio
+    }
+
+    /// Drive the output high
+    pub async fn set_high(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.set_state(true).await
+    }
+
+    /// Drive the output low
+    pub async fn set_low(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.set_state(false).await
+    }
+
+    /// Drive the output to a level
+    ///
+    /// # Arguments
+    ///
+    /// * `is_high` - true to drive the output high, false to drive it low
+    #[allow(clippy::await_holding_refcell_ref)] // Concurrent access fails with DriverBusy
+    pub async fn set_state(
+        &mut self,
+        is_high: bool,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        borrow_driver(self.npm1300)?
+            .set_gpio_mode(self.gpio, output_mode(is_high))
+            .await?;
+        self.is_high = is_high;
+        Ok(())
+    }
+
+    /// Toggle the output level
+    pub async fn toggle(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.set_state(!self.is_high).await
+    }
+
+    /// Check whether the output is set high
+    pub fn is_set_high(&self) -> bool {
+        self.is_high
+    }
+
+    /// Check whether the output is set low
+    pub fn is_set_low(&self) -> bool {
+        !self.is_high
+    }
+
+    /// Release the GPIO so it can be assigned to another function
+    ///
+    /// The GPIO keeps driving its current level.
+    ///
+    /// # Errors
+    ///
+    /// Returns the output back with `NPM1300Error::DriverBusy` if the driver is already
+    /// borrowed
+    pub fn release(self) -> Result<(), (Self, crate::NPM1300Error<I2c::Error>)> {
+        match borrow_driver(self.npm1300) {
+            Ok(mut npm1300) => {
+                npm1300.release_gpio(self.gpio);
+                Ok(())
+            }
+            Err(error) => Err((self, error)),
+        }
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs> ErrorType
+    for GpioOutput<'_, I2c, Delay>
+{
+    type Error = crate::NPM1300Error<I2c::Error>;
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs> OutputPin
+    for GpioOutput<'_, I2c, Delay>
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        block_on(self.set_state(false))
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        block_on(self.set_state(true))
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs> StatefulOutputPin
+    for GpioOutput<'_, I2c, Delay>
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_high)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high)
+    }
+}
+
+/// PMIC GPIO used as a digital input
+///
+/// Edges are detected with the GPI rising and falling edge event modes. The PMIC signals
+/// GPIO events on its interrupt line, which must be routed to a GPIO configured as
+/// [`GpioMode::GpoIrq`] and connected to the `Irq` pin of the host. The interrupt line is
+/// assumed to be active high.
+///
+/// The blocking [`InputPin`] trait is implemented for drivers that are generic over HAL pins.
+/// It busy-waits on the I2C transfer, so prefer the async [`is_high`](Self::is_high) and
+/// [`is_low`](Self::is_low) otherwise.
+///
+/// The input shares the driver through a [`RefCell`] like [`GpioOutput`]. The driver is not
+/// borrowed while waiting on the interrupt line, so other pins and direct driver calls can be
+/// used during a wait.
+pub struct GpioInput<
+    'a,
+    I2c: embedded_hal_async::i2c::I2c,
+    Delay: embedded_hal_async::delay::DelayNs,
+    Irq: Wait,
+> {
+    npm1300: &'a RefCell<crate::NPM1300<I2c, Delay>>,
+    gpio: Gpio,
+    irq: Irq,
+    /// Set when a cancelled wait could not record its armed event on the driver
+    is_disarm_pending: bool,
+}
+
+impl<
+        'a,
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+        Irq: Wait,
+    > GpioInput<'a, I2c, Delay, Irq>
+{
+    /// Use a GPIO as a digital input
+    ///
+    /// # Arguments
+    ///
+    /// * `npm1300` - The shared driver
+    /// * `gpio` - The GPIO to use
+    /// * `pull_down` - Whether to enable the GPIO pull-down
+    /// * `irq` - The host pin connected to the PMIC interrupt line
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidGpio` if `gpio` is [`Gpio::None`],
+    /// `NPM1300Error::GpioAlreadyInUse` if the GPIO is assigned to another function, or
+    /// `NPM1300Error::DriverBusy` if the driver is already borrowed
+    #[allow(clippy::await_holding_refcell_ref)] // Concurrent access fails with DriverBusy
+    pub async fn new(
+        npm1300: &'a RefCell<crate::NPM1300<I2c, Delay>>,
+        gpio: Gpio,
+        pull_down: GpioPullDown,
+        irq: Irq,
+    ) -> Result<Self, crate::NPM1300Error<I2c::Error>> {
+        let config = GpioConfigBuilder::new()
+            .mode(GpioMode::GpiInput)
+            .pull_down(pull_down)
+            .build();
+        borrow_driver(npm1300)?.configure_gpio(gpio, config).await?;
+
+        Ok(Self {
+            npm1300,
+            gpio,
+            irq,
+            is_disarm_pending: false,
+        })
+    }
+
+    /// Get the GPIO read by this input
+    pub fn gpio(&self) -> Gpio {
+        self.gpio
+    }
+
+    /// Check whether the input is high
+    #[allow(clippy::await_holding_refcell_ref)] // Concurrent access fails with DriverBusy
+    pub async fn is_high(&mut self) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        let mut npm1300 = self.driver()?;
+        Ok(matches!(
+            npm1300.get_gpio_status(self.gpio).await?,
+            GpioStatus::High
+        ))
+    }
+
+    /// Check whether the input is low
+    pub async fn is_low(&mut self) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        Ok(!self.is_high().await?)
+    }
+
+    /// Release the GPIO and return the interrupt pin
+    ///
+    /// # Errors
+    ///
+    /// Returns the input back with `NPM1300Error::DriverBusy` if the driver is already
+    /// borrowed
+    pub fn release(mut self) -> Result<Irq, (Self, crate::NPM1300Error<I2c::Error>)> {
+        match self.driver() {
+            Ok(mut npm1300) => npm1300.release_gpio(self.gpio),
+            Err(error) => return Err((self, error)),
+        }
+        Ok(self.irq)
+    }
+
+    /// Borrow the shared driver, handing it an event left armed by a cancelled wait
+    fn driver(
+        &mut self,
+    ) -> Result<RefMut<'a, crate::NPM1300<I2c, Delay>>, crate::NPM1300Error<I2c::Error>> {
+        let mut npm1300 = borrow_driver(self.npm1300)?;
+        if self.is_disarm_pending {
+            npm1300.gpio_events_to_disarm |= self.event_mask();
+            self.is_disarm_pending = false;
+        }
+        Ok(npm1300)
+    }
+
+    /// Wait for an edge event
+    ///
+    /// The event is armed before the level is checked, so that an edge arriving in between
+    /// is not lost. If the wait fails or is cancelled, the GPIO is put back in GPI input mode
+    /// and its event interrupt is disabled by the next driver GPIO call.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - [`GpioMode::GpiEventRise`] or [`GpioMode::GpiEventFall`]
+    /// * `until_high` - Return as soon as the input is at this level once the event is armed,
+    ///   `None` to wait for the edge only
+    #[allow(clippy::await_holding_refcell_ref)] // Concurrent access fails with DriverBusy
+    async fn wait_for_event(
+        &mut self,
+        mode: GpioMode,
+        until_high: Option<bool>,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.driver()?.disarm_pending_gpio_events().await?;
+        let mut event = ArmedEvent {
+            input: self,
+            is_armed: true,
+        };
+        event.input.arm_event(mode).await?;
+
+        if let Some(is_high) = until_high {
+            if event.input.is_high().await? == is_high {
+                return event.disarm().await;
+            }
+        }
+
+        let mask = event.input.event_mask();
+        loop {
+            event
+                .input
+                .irq
+                .wait_for_high()
+                .await
+                .map_err(|_| crate::NPM1300Error::InterruptPin)?;
+            if event.input.driver()?.get_gpio_events().await? & mask != 0 {
+                break;
+            }
+            // The interrupt line is held by another event, wait for it to be handled
+            event
+                .input
+                .irq
+                .wait_for_low()
+                .await
+                .map_err(|_| crate::NPM1300Error::InterruptPin)?;
+        }
+        event.disarm().await
+    }
+
+    /// Switch the GPIO to an edge event mode with a cleared event and its interrupt enabled
+    #[allow(clippy::await_holding_refcell_ref)] // Concurrent access fails with DriverBusy
+    async fn arm_event(&mut self, mode: GpioMode) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let mask = self.event_mask();
+        let mut npm1300 = self.driver()?;
+        npm1300.set_gpio_mode(self.gpio, mode).await?;
+        npm1300.clear_gpio_event_mask(mask).await?;
+        npm1300.enable_gpio_interrupts(mask).await
+    }
+
+    /// Put the GPIO back in GPI input mode with its event cleared and its interrupt disabled
+    #[allow(clippy::await_holding_refcell_ref)] // Concurrent access fails with DriverBusy
+    async fn disarm_event(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let mask = self.event_mask();
+        let mut npm1300 = self.driver()?;
+        npm1300.set_gpio_mode(self.gpio, GpioMode::GpiInput).await?;
+        npm1300.disable_gpio_interrupts(mask).await?;
+        npm1300.clear_gpio_event_mask(mask).await
+    }
+
+    /// Bit of this GPIO in the GPIO event registers
+    fn event_mask(&self) -> u8 {
+        // GPIO inputs are never created for Gpio::None
+        1 << self.gpio.index().unwrap_or_default()
+    }
+}
+
+/// Edge event armed by [`GpioInput::wait_for_event`]
+///
+/// When dropped without [`disarm`](Self::disarm), i.e. when the wait fails or its future is
+/// dropped, the event is recorded for
+/// [`NPM1300::disarm_pending_gpio_events`](crate::NPM1300::disarm_pending_gpio_events), or on
+/// the input if the driver is borrowed. Drop does no I/O, since the bus may be held by another
+/// task.
+struct ArmedEvent<
+    'g,
+    'a,
+    I2c: embedded_hal_async::i2c::I2c,
+    Delay: embedded_hal_async::delay::DelayNs,
+    Irq: Wait,
+> {
+    input: &'g mut GpioInput<'a, I2c, Delay, Irq>,
+    is_armed: bool,
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs, Irq: Wait>
+    ArmedEvent<'_, '_, I2c, Delay, Irq>
+{
+    /// Disarm the event
+    async fn disarm(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.is_armed = false;
+        self.input.disarm_event().await
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs, Irq: Wait> Drop
+    for ArmedEvent<'_, '_, I2c, Delay, Irq>
+{
+    fn drop(&mut self) {
+        if self.is_armed {
+            let mask = self.input.event_mask();
+            match self.input.npm1300.try_borrow_mut() {
+                Ok(mut npm1300) => npm1300.gpio_events_to_disarm |= mask,
+                Err(_) => self.input.is_disarm_pending = true,
+            }
+        }
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs, Irq: Wait>
+    ErrorType for GpioInput<'_, I2c, Delay, Irq>
+{
+    type Error = crate::NPM1300Error<I2c::Error>;
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs, Irq: Wait>
+    InputPin for GpioInput<'_, I2c, Delay, Irq>
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        block_on(GpioInput::is_high(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        block_on(GpioInput::is_low(self))
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs, Irq: Wait> Wait
+    for GpioInput<'_, I2c, Delay, Irq>
+{
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_event(GpioMode::GpiEventRise, Some(true))
+            .await
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_event(GpioMode::GpiEventFall, Some(false))
+            .await
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_event(GpioMode::GpiEventRise, None).await
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_event(GpioMode::GpiEventFall, None).await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        // Arm the opposite edge, a level change before it is armed counts as the edge
+        if GpioInput::is_high(self).await? {
+            self.wait_for_event(GpioMode::GpiEventFall, Some(false))
+                .await
+        } else {
+            self.wait_for_event(GpioMode::GpiEventRise, Some(true))
+                .await
+        }
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Disarm the GPIO edge events left armed by cancelled or failed waits
+    ///
+    /// A [`GpioInput`] wait that is cancelled cannot do I/O while being dropped, so its GPIO is
+    /// left in an event mode with the event interrupt enabled. This puts such GPIOs back in GPI
+    /// input mode, disables their event interrupts and clears their events. It is called
+    /// automatically by the next GPIO wait and by [`configure_gpio`](Self::configure_gpio).
+    pub async fn disarm_pending_gpio_events(
+        &mut self,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let mask = self.gpio_events_to_disarm;
+        if mask == 0 {
+            return Ok(());
+        }
+        for gpio in Gpio::ALL {
+            if gpio.index().is_some_and(|index| mask & (1 << index) != 0) {
+                self.set_gpio_mode(gpio, GpioMode::GpiInput).await?;
+            }
+        }
+        self.disable_gpio_interrupts(mask).await?;
+        self.clear_gpio_event_mask(mask).await?;
+        self.gpio_events_to_disarm = 0;
+        Ok(())
+    }
+
+    /// Set the mode of a GPIO without changing its other settings
+    async fn set_gpio_mode(
+        &mut self,
+        gpio: Gpio,
+        mode: GpioMode,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let index = gpio.index().ok_or(crate::NPM1300Error::InvalidGpio)?;
+        self.device
+            .gpios()
+            .gpiomode(index)
+            .write_async(|reg| reg.set_gpiomode(mode))
+            .await
+    }
+}
+
+/// GPO mode driving a level
+fn output_mode(is_high: bool) -> GpioMode {
+    if is_high {
+        GpioMode::GpoLogic1
+    } else {
+        GpioMode::GpoLogic0
+    }
+}
@@ -1,5 +1,7 @@
+use crate::buck::BuckId;
+
 /// GPIOs available on the nPM1300
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum Gpio {
     None = 0,
@@ -10,6 +12,31 @@ pub enum Gpio {
     Gpio4 = 5,
 }
 
+/// Number of GPIOs on the nPM1300
+pub const GPIO_COUNT: usize = 5;
+
+impl Gpio {
+    /// All GPIOs, ordered by index
+    pub const ALL: [Gpio; GPIO_COUNT] = [
+        Self::Gpio0,
+        Self::Gpio1,
+        Self::Gpio2,
+        Self::Gpio3,
+        Self::Gpio4,
+    ];
+
+    /// Index of the GPIO in the repeated GPIO registers, `None` for [`Gpio::None`]
+    ///
+    /// GPIOs are 1-indexed in the nPM1300 so we subtract 1 from the GPIO number
+    /// to get the register index
+    pub(crate) fn index(self) -> Option<usize> {
+        match self {
+            Self::None => None,
+            gpio => Some(usize::from(u8::from(gpio) - 1)),
+        }
+    }
+}
+
 // Add conversion from u8
 impl TryFrom<u8> for Gpio {
     type Error = ();
@@ -34,8 +61,41 @@ impl From<Gpio> for u8 {
     }
 }
 
+/// Function a GPIO is assigned to by the driver
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum GpioFunction {
+    /// General purpose input or output configured with `configure_gpio`
+    General,
+    /// Interrupt output
+    Interrupt,
+    /// Reset output
+    Reset,
+    /// Power failure warning output
+    PowerFailureWarning,
+    /// BUCK regulator enable input
+    BuckEnable(BuckId),
+    /// BUCK regulator retention voltage select input
+    BuckRetention(BuckId),
+    /// BUCK regulator forced PWM mode input
+    BuckForcedPwm(BuckId),
+    /// Second button of the two-button long press reset, only available on GPIO0
+    ShipHoldReset,
+}
+
+impl From<GpioMode> for GpioFunction {
+    fn from(mode: GpioMode) -> Self {
+        match mode {
+            GpioMode::GpoIrq => Self::Interrupt,
+            GpioMode::GpoReset => Self::Reset,
+            GpioMode::GpoPowerLossWarning => Self::PowerFailureWarning,
+            _ => Self::General,
+        }
+    }
+}
+
 /// GPIOs polarity
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioPolarity {
     NotInverted = 0,
@@ -63,7 +123,7 @@ impl From<GpioPolarity> for u8 {
 }
 
 /// GPIO mode configuration
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioMode {
     /// GPI Input
@@ -117,7 +177,7 @@ impl From<GpioMode> for u8 {
 }
 
 /// GPIO Drive Strength Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioDriveStrength {
     /// 1mA
@@ -145,7 +205,7 @@ impl From<GpioDriveStrength> for u8 {
 }
 
 /// GPIO Pull-up Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioPullUp {
     Disable = 0,
@@ -171,7 +231,7 @@ impl From<GpioPullUp> for u8 {
 }
 
 /// GPIO Pull-down Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioPullDown {
     Disable = 0,
@@ -198,7 +258,7 @@ impl From<GpioPullDown> for u8 {
 }
 
 /// GPIO Open Drain Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioOpenDrain {
     Disable = 0,
@@ -224,7 +284,7 @@ impl From<GpioOpenDrain> for u8 {
 }
 
 /// GPIO Debounce Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioDebounce {
     Disable = 0,
@@ -250,7 +310,7 @@ impl From<GpioDebounce> for u8 {
 }
 
 /// GPIO input status
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioStatus {
     Low = 0,
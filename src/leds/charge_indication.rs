@@ -0,0 +1,166 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use super::{LedId, LedMode, LedPattern, LedRepeat, LedStep};
+use crate::charger::{ChargerErrorReason, ChargerStatus};
+
+/// One second with the LED off
+const OFF_STEPS: &[LedStep] = &[LedStep::off(1000)];
+
+/// One second with the LED on
+const SOLID_STEPS: &[LedStep] = &[LedStep::on(1000)];
+
+/// LED state indicating the charger state
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LedIndication {
+    /// LED off, when not charging or charging is complete
+    Off,
+    /// LED on, while charging with constant current or trickle charging
+    Solid,
+    /// Slow blink, while charging with constant voltage
+    BlinkSlow,
+    /// Fast blink, on a charger error such as an NTC fault
+    BlinkFast,
+    /// Low battery code, when not charging and the battery voltage is low
+    LowBattery,
+}
+
+impl LedIndication {
+    /// Get one cycle of the pattern showing this indication
+    ///
+    /// Every cycle lasts about one second so the indication can be refreshed between cycles.
+    pub fn pattern(&self) -> LedPattern<'static> {
+        match self {
+            Self::Off => LedPattern {
+                steps: OFF_STEPS,
+                repeat: LedRepeat::Times(1),
+            },
+            Self::Solid => LedPattern {
+                steps: SOLID_STEPS,
+                repeat: LedRepeat::Times(1),
+            },
+            Self::BlinkSlow => LedPattern {
+                repeat: LedRepeat::Times(1),
+                ..LedPattern::BLINK_SLOW
+            },
+            Self::BlinkFast => LedPattern {
+                repeat: LedRepeat::Times(5),
+                ..LedPattern::BLINK_FAST
+            },
+            Self::LowBattery => LedPattern::LOW_BATTERY,
+        }
+    }
+}
+
+/// Host-driven charge LED policy
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ChargeLedPolicy {
+    /// Battery voltage below which a low battery is indicated when not charging, in volts
+    pub low_battery_threshold_v: f32,
+}
+
+impl Default for ChargeLedPolicy {
+    fn default() -> Self {
+        Self {
+            low_battery_threshold_v: 3.5,
+        }
+    }
+}
+
+impl ChargeLedPolicy {
+    /// Map the charger state to an LED indication
+    ///
+    /// Charger errors take precedence, then the charging phase, then the battery voltage.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The charger status, see [`NPM1300::get_charger_status`](crate::NPM1300::get_charger_status)
+    /// * `error` - The charger error reason, see
+    ///   [`NPM1300::get_charger_error_reason_and_sensor_value`](crate::NPM1300::get_charger_error_reason_and_sensor_value)
+    /// * `vbat_v` - The battery voltage in volts
+    pub fn indication(
+        &self,
+        status: &ChargerStatus,
+        error: &ChargerErrorReason,
+        vbat_v: f32,
+    ) -> LedIndication {
+        let has_error = error.ntc_sensor_error
+            || error.vbat_sensor_error
+            || error.vbat_low_error
+            || error.vtrickle_error
+            || error.measurement_timeout_error
+            || error.charge_timeout_error
+            || error.trickle_timeout_error;
+
+        if has_error {
+            LedIndication::BlinkFast
+        } else if !status.is_battery_present || status.is_charging_complete {
+            LedIndication::Off
+        } else if status.is_constant_current_charging || status.is_trickle_charging {
+            LedIndication::Solid
+        } else if status.is_constant_voltage_charging {
+            LedIndication::BlinkSlow
+        } else if vbat_v < self.low_battery_threshold_v {
+            LedIndication::LowBattery
+        } else {
+            LedIndication::Off
+        }
+    }
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Get the LED indication for the current charger state
+    ///
+    /// Reads the charger status and error reason and measures the battery voltage.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy mapping the charger state to an LED indication
+    pub async fn get_charge_led_indication(
+        &mut self,
+        policy: &ChargeLedPolicy,
+    ) -> Result<LedIndication, crate::NPM1300Error<I2c::Error>> {
+        let status = self.get_charger_status().await?;
+        let (error, _) = self.get_charger_error_reason_and_sensor_value().await?;
+        let vbat_v = self.measure_vbat().await?;
+        Ok(policy.indication(&status, &error, vbat_v))
+    }
+
+    /// Drive an LED from the charger state
+    ///
+    /// Switches the LED to host mode, replacing the hardware [`LedMode::Charging`] and
+    /// [`LedMode::ChargingError`] indications, and refreshes the indication about every
+    /// second until cancelled. The LED is then turned off and switched to `restore_mode`
+    /// if given.
+    ///
+    /// # Arguments
+    ///
+    /// * `led` - The LED to drive
+    /// * `policy` - The policy mapping the charger state to an LED indication
+    /// * `cancel` - Set to `true` from another task to stop driving the LED
+    /// * `restore_mode` - The LED mode to set after cancellation
+    pub async fn run_charge_led_policy(
+        &mut self,
+        led: LedId,
+        policy: &ChargeLedPolicy,
+        cancel: &AtomicBool,
+        restore_mode: Option<LedMode>,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.configure_led_mode(led, LedMode::Host).await?;
+
+        while !cancel.load(Ordering::Relaxed) {
+            let indication = self.get_charge_led_indication(policy).await?;
+            self.run_led_pattern(led, &indication.pattern(), cancel)
+                .await?;
+        }
+
+        self.drive_led(led, false).await?;
+        if let Some(mode) = restore_mode {
+            self.configure_led_mode(led, mode).await?;
+        }
+        Ok(())
+    }
+}
@@ -1,128 +1,139 @@
 use crate::common::Task;
 
+mod charge_indication;
+mod pattern;
 mod types;
 
+pub use charge_indication::*;
+pub use pattern::*;
 // Re-export everything in types.rs
 pub use types::*;
 
+/// Handle to one of the LED drivers
+///
+/// Created with [`NPM1300::led`](crate::NPM1300::led).
+pub struct Led<'a, I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs> {
+    npm1300: &'a mut crate::NPM1300<I2c, Delay>,
+    id: LedId,
+}
+
 impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
+    Led<'_, I2c, Delay>
 {
-    /// Configure LED driver
-    ///
-    /// # Arguments
-    /// * `led` - LED number
-    /// * `mode` - LED mode configuration see [`LedMode`]
-    async fn configure_led_mode(
-        &mut self,
-        led: usize,
-        mode: LedMode,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        // Configure LED mode
-        self.device
-            .leddrv()
-            .leddrvmodesel(led)
-            .write_async(|reg| reg.set_leddrvmodesel(mode))
-            .await?;
-        Ok(())
+    /// Get the LED this handle controls
+    pub fn id(&self) -> LedId {
+        self.id
     }
 
-    /// Configure LED0 mode
+    /// Configure the LED mode
     ///
     /// # Arguments
     /// * `mode` - LED mode configuration, see [`LedMode`]
-    pub async fn configure_led0_mode(
-        &mut self,
-        mode: LedMode,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_led_mode(0, mode).await
+    pub async fn set_mode(&mut self, mode: LedMode) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.npm1300.configure_led_mode(self.id, mode).await
     }
 
-    /// Configure LED1 mode
+    /// Get the LED mode
     ///
-    /// # Arguments
-    /// * `mode` - LED mode configuration, see [`LedMode`]
-    pub async fn configure_led1_mode(
-        &mut self,
-        mode: LedMode,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_led_mode(1, mode).await
+    /// # Returns
+    /// * `Ok(LedMode)` - The configured LED mode
+    /// * `Err(NPM1300Error)` - An error occurred while reading the LED mode
+    pub async fn mode(&mut self) -> Result<LedMode, crate::NPM1300Error<I2c::Error>> {
+        let mode = self
+            .npm1300
+            .device
+            .leddrv()
+            .leddrvmodesel(self.id.index())
+            .read_async()
+            .await?;
+        // Since we successfully read the register, we can safely unwrap the mode
+        Ok(mode.leddrvmodesel().unwrap())
     }
 
-    /// Configure LED2 mode
+    /// Turn the LED on
     ///
-    /// # Arguments
-    /// * `mode` - LED mode configuration, see [`LedMode`]
-    pub async fn configure_led2_mode(
-        &mut self,
-        mode: LedMode,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_led_mode(2, mode).await
+    /// Only works when the LED is configured in host mode
+    pub async fn on(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.npm1300.drive_led(self.id, true).await
     }
 
-    /// Enable LED0
+    /// Turn the LED off
     ///
-    /// Only works when LED0 is configured in host mode
-    pub async fn enable_led0(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .leddrv()
-            .leddrvset(0)
-            .dispatch_async(|command| command.set_leddrvset(Task::Trigger))
-            .await
+    /// Only works when the LED is configured in host mode
+    pub async fn off(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.npm1300.drive_led(self.id, false).await
     }
 
-    /// Disable LED0
+    /// Toggle the LED
     ///
-    /// Only works when LED0 is configured in host mode
-    pub async fn disable_led0(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .leddrv()
-            .leddrvclr(0)
-            .dispatch_async(|command| command.set_leddrvclr(Task::Trigger))
-            .await
+    /// Only works when the LED is configured in host mode
+    pub async fn toggle(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let is_on = self.is_on();
+        self.npm1300.drive_led(self.id, !is_on).await
     }
 
-    /// Enable LED1
+    /// Check whether the LED was last turned on
     ///
-    /// Only works when LED1 is configured in host mode
-    pub async fn enable_led1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .leddrv()
-            .leddrvset(1)
-            .dispatch_async(|command| command.set_leddrvset(Task::Trigger))
-            .await
+    /// The state is tracked in software since the LED driver state cannot be read back. It
+    /// only reflects the host mode state set through this driver and starts as off.
+    pub fn is_on(&self) -> bool {
+        self.npm1300.led_states[self.id.index()]
     }
+}
 
-    /// Disable LED1
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Get a handle to an LED driver
     ///
-    /// Only works when LED1 is configured in host mode
-    pub async fn disable_led1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .leddrv()
-            .leddrvclr(1)
-            .dispatch_async(|command| command.set_leddrvclr(Task::Trigger))
-            .await
+    /// # Arguments
+    ///
+    /// * `id` - The LED to control. See [`LedId`] for available values.
+    pub fn led(&mut self, id: LedId) -> Led<'_, I2c, Delay> {
+        Led { npm1300: self, id }
     }
 
-    /// Enable LED2
+    /// Configure LED driver
     ///
-    /// Only works when LED2 is configured in host mode
-    pub async fn enable_led2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+    /// # Arguments
+    /// * `led` - LED to configure
+    /// * `mode` - LED mode configuration see [`LedMode`]
+    async fn configure_led_mode(
+        &mut self,
+        led: LedId,
+        mode: LedMode,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        // Configure LED mode
         self.device
             .leddrv()
-            .leddrvset(2)
-            .dispatch_async(|command| command.set_leddrvset(Task::Trigger))
-            .await
+            .leddrvmodesel(led.index())
+            .write_async(|reg| reg.set_leddrvmodesel(mode))
+            .await?;
+        Ok(())
     }
 
-    /// Disable LED2
+    /// Turn an LED on or off
     ///
-    /// Only works when LED2 is configured in host mode
-    pub async fn disable_led2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.device
-            .leddrv()
-            .leddrvclr(2)
-            .dispatch_async(|command| command.set_leddrvclr(Task::Trigger))
-            .await
+    /// Only works when the LED is configured in host mode
+    async fn drive_led(
+        &mut self,
+        led: LedId,
+        is_on: bool,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if is_on {
+            self.device
+                .leddrv()
+                .leddrvset(led.index())
+                .dispatch_async(|command| command.set_leddrvset(Task::Trigger))
+                .await?;
+        } else {
+            self.device
+                .leddrv()
+                .leddrvclr(led.index())
+                .dispatch_async(|command| command.set_leddrvclr(Task::Trigger))
+                .await?;
+        }
+        self.led_states[led.index()] = is_on;
+        Ok(())
     }
 }
@@ -0,0 +1,178 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use super::{LedId, LedMode};
+
+/// Longest time the pattern player waits before checking for cancellation, in milliseconds
+const CANCEL_POLL_INTERVAL_MS: u32 = 10;
+
+/// A single step of an [`LedPattern`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct LedStep {
+    /// Whether the LED is on during the step
+    pub is_on: bool,
+    /// Duration of the step in milliseconds
+    pub duration_ms: u32,
+}
+
+impl LedStep {
+    /// LED on for a duration in milliseconds
+    pub const fn on(duration_ms: u32) -> Self {
+        Self {
+            is_on: true,
+            duration_ms,
+        }
+    }
+
+    /// LED off for a duration in milliseconds
+    pub const fn off(duration_ms: u32) -> Self {
+        Self {
+            is_on: false,
+            duration_ms,
+        }
+    }
+}
+
+/// Number of times an [`LedPattern`] is played
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LedRepeat {
+    /// Play the steps a number of times
+    Times(u16),
+    /// Play the steps until cancelled
+    Forever,
+}
+
+/// Timed on/off sequence played on an LED in host mode
+///
+/// The LED drivers are either on or off, so patterns cannot change the LED brightness.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct LedPattern<'a> {
+    pub steps: &'a [LedStep],
+    pub repeat: LedRepeat,
+}
+
+impl LedPattern<'static> {
+    /// Slow blink, 500 ms on and 500 ms off, until cancelled
+    pub const BLINK_SLOW: Self = Self {
+        steps: &[LedStep::on(500), LedStep::off(500)],
+        repeat: LedRepeat::Forever,
+    };
+
+    /// Fast blink, 100 ms on and 100 ms off, until cancelled
+    pub const BLINK_FAST: Self = Self {
+        steps: &[LedStep::on(100), LedStep::off(100)],
+        repeat: LedRepeat::Forever,
+    };
+
+    /// Heartbeat, two short blinks followed by a pause, until cancelled
+    pub const HEARTBEAT: Self = Self {
+        steps: &[
+            LedStep::on(100),
+            LedStep::off(100),
+            LedStep::on(100),
+            LedStep::off(700),
+        ],
+        repeat: LedRepeat::Forever,
+    };
+
+    /// Low battery code, three short blinks followed by a pause, played once
+    pub const LOW_BATTERY: Self = Self {
+        steps: &[
+            LedStep::on(150),
+            LedStep::off(150),
+            LedStep::on(150),
+            LedStep::off(150),
+            LedStep::on(150),
+            LedStep::off(1000),
+        ],
+        repeat: LedRepeat::Times(1),
+    };
+
+    /// Error code, five fast blinks
+    pub const ERROR: Self = Self {
+        steps: &[LedStep::on(100), LedStep::off(100)],
+        repeat: LedRepeat::Times(5),
+    };
+}
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Play an LED pattern
+    ///
+    /// Switches the LED to host mode and plays the pattern steps. The LED is turned off when
+    /// the pattern completes or is cancelled, then switched to `restore_mode` if given, for
+    /// instance [`LedMode::Charging`] to return the LED to charger indication.
+    ///
+    /// # Arguments
+    ///
+    /// * `led` - The LED to play the pattern on
+    /// * `pattern` - The pattern to play, see [`LedPattern`] for predefined patterns
+    /// * `cancel` - Set to `true` from another task to stop the pattern, it is checked at
+    ///   least every 10 ms
+    /// * `restore_mode` - The LED mode to set after the pattern
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The pattern completed
+    /// * `Ok(false)` - The pattern was cancelled
+    /// * `Err(NPM1300Error)` - An error occurred while driving the LED
+    pub async fn play_led_pattern(
+        &mut self,
+        led: LedId,
+        pattern: &LedPattern<'_>,
+        cancel: &AtomicBool,
+        restore_mode: Option<LedMode>,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        self.configure_led_mode(led, LedMode::Host).await?;
+
+        let completed = self.run_led_pattern(led, pattern, cancel).await?;
+
+        self.drive_led(led, false).await?;
+        if let Some(mode) = restore_mode {
+            self.configure_led_mode(led, mode).await?;
+        }
+        Ok(completed)
+    }
+
+    /// Play the pattern steps, returning `false` if cancelled
+    pub(super) async fn run_led_pattern(
+        &mut self,
+        led: LedId,
+        pattern: &LedPattern<'_>,
+        cancel: &AtomicBool,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        let mut remaining = pattern.repeat;
+        loop {
+            match remaining {
+                LedRepeat::Times(0) => return Ok(true),
+                LedRepeat::Times(times) => remaining = LedRepeat::Times(times - 1),
+                LedRepeat::Forever => {}
+            }
+
+            for step in pattern.steps {
+                if cancel.load(Ordering::Relaxed) {
+                    return Ok(false);
+                }
+                self.drive_led(led, step.is_on).await?;
+
+                let mut remaining_ms = step.duration_ms;
+                while remaining_ms > 0 {
+                    if cancel.load(Ordering::Relaxed) {
+                        return Ok(false);
+                    }
+                    let delay_ms = remaining_ms.min(CANCEL_POLL_INTERVAL_MS);
+                    self.delay.delay_ms(delay_ms).await;
+                    remaining_ms -= delay_ms;
+                }
+            }
+
+            // A pattern without steps would otherwise spin forever
+            if pattern.steps.is_empty() {
+                return Ok(true);
+            }
+        }
+    }
+}
@@ -1,5 +1,28 @@
+/// Number of LED drivers on the nPM1300
+pub const LED_COUNT: usize = 3;
+
+/// LEDs available on the nPM1300
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LedId {
+    Led0,
+    Led1,
+    Led2,
+}
+
+impl LedId {
+    /// Index of the LED in the repeated LED driver registers
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Self::Led0 => 0,
+            Self::Led1 => 1,
+            Self::Led2 => 2,
+        }
+    }
+}
+
 /// LED mode configuration
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum LedMode {
     /// Error condition from charger
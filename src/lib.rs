@@ -9,8 +9,10 @@ pub mod buck;
 pub mod charger;
 pub mod gpios;
 pub mod leds;
+pub mod loadsw;
 pub mod mainreg;
 pub mod pof;
+pub mod sequence;
 pub mod ship;
 pub mod sysreg;
 
@@ -27,16 +29,50 @@ pub enum NPM1300Error<I2cError> {
     InvalidChargeMode,
     #[error("invalid NTC threshold")]
     InvalidNtcThreshold,
+    #[error("NTC thresholds must be ordered cold < cool < warm < hot")]
+    InvalidNtcThresholdOrder,
     #[error("invalid die temperature stop/resume threshold")]
     InvalidDieTemperatureThreshold,
     #[error("invalid NTC beta")]
     InvalidNtcBeta,
+    #[error("no NTC thermistor configured")]
+    NtcThermistorNotConfigured,
     #[error(
         "invalid VBAT measurement delay value, it must be between 4 and 514 and a multiple of 2"
     )]
     InvalidVbatMeasurementDelayValue,
     #[error("invalid VSYS threshold")]
     InvalidPofVsysThreshold,
+    #[error("invalid GPIO")]
+    InvalidGpio,
+    #[error("GPIO {0:?} is already in use")]
+    GpioAlreadyInUse(gpios::Gpio),
+    #[error("GPIO {0:?} has an invalid mode")]
+    InvalidGpioMode(gpios::Gpio),
+    #[error("interrupt pin error")]
+    InterruptPin,
+    #[error("the driver is already borrowed")]
+    DriverBusy,
+    #[error("invalid BUCK voltage {0} mV, it must be between 1000 and 3300 mV")]
+    InvalidBuckVoltage(u16),
+    #[error("invalid BUCK ramp step {0} mV, it must be a non-zero multiple of 100 mV")]
+    InvalidBuckRampStep(u16),
+    #[error("BUCK {0:?} is disabled")]
+    BuckDisabled(buck::BuckId),
+    #[error("BUCK {0:?} power is not good")]
+    BuckPowerNotGood(buck::BuckId),
+    #[error("timed out waiting for BUCK {0:?} power-good")]
+    BuckPowerGoodTimeout(buck::BuckId),
+    #[error("charge current and discharge current limit must be configured to convert IBAT")]
+    IbatCurrentLimitsNotConfigured,
+    #[error("invalid power budget configuration")]
+    InvalidPowerBudgetConfig,
+    #[error(
+        "VBUS is present, the PMIC does not enter ship or hibernate mode while VBUS is connected"
+    )]
+    VbusPresent,
+    #[error("the PMIC did not enter {0:?} mode")]
+    LowPowerModeNotEntered(ship::LowPowerMode),
 }
 
 #[derive(Debug)]
@@ -48,6 +84,12 @@ pub struct NPM1300<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async:
     device: Device<DeviceInterface<I2c>>,
     delay: Delay,
     ntc_beta: Option<f32>,
+    charge_current_ma: Option<u16>,
+    discharge_current_limit: Option<charger::DischargeCurrentLimit>,
+    gpio_functions: [Option<gpios::GpioFunction>; gpios::GPIO_COUNT],
+    /// GPIO edge events left armed by cancelled waits, bit `n` for GPIO`n`
+    gpio_events_to_disarm: u8,
+    led_states: [bool; leds::LED_COUNT],
 }
 
 impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
@@ -58,6 +100,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             device: Device::new(DeviceInterface { i2c }),
             delay,
             ntc_beta: None,
+            charge_current_ma: None,
+            discharge_current_limit: None,
+            gpio_functions: [None; gpios::GPIO_COUNT],
+            gpio_events_to_disarm: 0,
+            led_states: [false; leds::LED_COUNT],
         }
     }
 }
@@ -0,0 +1,59 @@
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+use crate::common::Task;
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Enable a load switch
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The load switch to enable
+    pub async fn enable_load_switch(
+        &mut self,
+        id: LoadSwitchId,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .taskldswset(id.index())
+            .dispatch_async(|command| command.set_taskldswset(Task::Trigger))
+            .await
+    }
+
+    /// Disable a load switch
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The load switch to disable
+    pub async fn disable_load_switch(
+        &mut self,
+        id: LoadSwitchId,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .taskldswclr(id.index())
+            .dispatch_async(|command| command.set_taskldswclr(Task::Trigger))
+            .await
+    }
+
+    /// Check whether a load switch is powered up
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the load switch is powered up, as a load switch or as an LDO
+    /// * `Err(NPM1300Error)` - An error occurred while reading the load switch status
+    pub async fn is_load_switch_enabled(
+        &mut self,
+        id: LoadSwitchId,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        let status = self.device.ldsw().ldswstatus().read_async().await?;
+        Ok(match id {
+            LoadSwitchId::Ldsw1 => status.ldsw_1_pwrupldsw() || status.ldsw_1_pwrupldo(),
+            LoadSwitchId::Ldsw2 => status.ldsw_2_pwrupldsw() || status.ldsw_2_pwrupldo(),
+        })
+    }
+}
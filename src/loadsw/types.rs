@@ -0,0 +1,17 @@
+/// Load switches available on the nPM1300
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LoadSwitchId {
+    Ldsw1,
+    Ldsw2,
+}
+
+impl LoadSwitchId {
+    /// Index of the load switch in the repeated load switch registers
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Self::Ldsw1 => 0,
+            Self::Ldsw2 => 1,
+        }
+    }
+}
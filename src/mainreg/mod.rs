@@ -50,6 +50,37 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Get the pending VBUSIN0 events
+    ///
+    /// # Returns
+    ///
+    /// The pending VBUS detection and over/under-voltage events, see [`Vbusin0EventMask`]
+    pub async fn get_vbusin0_events(
+        &mut self,
+    ) -> Result<Vbusin0EventMask, crate::NPM1300Error<I2c::Error>> {
+        let events = self.device.main().eventsvbusin_0_clr().read_async().await?;
+        let mut mask = Vbusin0EventMask::empty();
+        mask.set(Vbusin0EventMask::VBUS_DETECTED, events.eventvbusdetected());
+        mask.set(Vbusin0EventMask::VBUS_REMOVED, events.eventvbusremoved());
+        mask.set(
+            Vbusin0EventMask::OVRVOLT_DETECTED,
+            events.eventvbusovrvoltdetected(),
+        );
+        mask.set(
+            Vbusin0EventMask::OVRVOLT_REMOVED,
+            events.eventvbusovrvoltremoved(),
+        );
+        mask.set(
+            Vbusin0EventMask::UNDERVOLT_DETECTED,
+            events.eventvbusundervoltdetected(),
+        );
+        mask.set(
+            Vbusin0EventMask::UNDERVOLT_REMOVED,
+            events.eventvbusundervoltremoved(),
+        );
+        Ok(mask)
+    }
+
     pub async fn enable_vbusin0_interrupts(
         &mut self,
         mask: Vbusin0EventMask,
@@ -94,6 +125,20 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Clear all pending events
+    pub async fn clear_all_events(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.clear_vbusin0_event_mask(Vbusin0EventMask::all())
+            .await?;
+        self.clear_vbusin1_event_mask(Vbusin1EventMask::all())
+            .await?;
+        self.clear_adc_event_mask(0xFF).await?;
+        self.clear_bcharger0_event_mask(0xFF).await?;
+        self.clear_bcharger1_event_mask(0xFF).await?;
+        self.clear_bcharger2_event_mask(0xFF).await?;
+        self.clear_shphld_event().await?;
+        self.clear_gpio_event_mask(0xFF).await
+    }
+
     pub async fn set_adc_event_mask(
         &mut self,
         mask: u8,
@@ -116,6 +161,21 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Get the pending ADC events
+    ///
+    /// # Returns
+    ///
+    /// A bit mask of the pending ADC events
+    pub async fn get_adc_events(&mut self) -> Result<u8, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .device
+            .main()
+            .eventsadcclr()
+            .read_async()
+            .await?
+            .value())
+    }
+
     pub async fn enable_adc_interrupts(
         &mut self,
         mask: u8,
@@ -286,6 +346,20 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Check whether a SHPHLD event is pending
+    pub async fn is_shphld_event_pending(
+        &mut self,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .device
+            .main()
+            .eventsshphldclr()
+            .read_async()
+            .await?
+            .eventshphld()
+            == 1)
+    }
+
     pub async fn enable_shphld_interrupt(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         self.device
             .main()
@@ -326,6 +400,18 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Get the pending VBUSIN1 events
+    ///
+    /// # Returns
+    ///
+    /// The pending thermal and CC events, see [`Vbusin1EventMask`]
+    pub async fn get_vbusin1_events(
+        &mut self,
+    ) -> Result<Vbusin1EventMask, crate::NPM1300Error<I2c::Error>> {
+        let events = self.device.main().eventsvbusin_1_clr().read_async().await?;
+        Ok(Vbusin1EventMask::from_bits_truncate(events.value()))
+    }
+
     pub async fn enable_vbusin1_interrupts(
         &mut self,
         mask: Vbusin1EventMask,
@@ -370,6 +456,21 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Get the pending GPIO events
+    ///
+    /// # Returns
+    ///
+    /// A bit mask of the pending events, bit `n` is set for an event on GPIO`n`
+    pub async fn get_gpio_events(&mut self) -> Result<u8, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .device
+            .main()
+            .eventsgpioclr()
+            .read_async()
+            .await?
+            .value())
+    }
+
     pub async fn enable_gpio_interrupts(
         &mut self,
         mask: u8,
@@ -1,7 +1,7 @@
 use bitflags::bitflags;
 
 bitflags! {
-    #[derive(Default)]
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
     pub struct Vbusin0EventMask: u8 {
         const VBUS_DETECTED          = 1 << 0;
         const VBUS_REMOVED           = 1 << 1;
@@ -13,7 +13,7 @@ bitflags! {
 }
 
 bitflags! {
-    #[derive(Default)]
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
     pub struct Vbusin1EventMask: u8 {
         const THERMAL_WARN_DETECTED      = 1 << 0;
         const THERMAL_WARN_REMOVED       = 1 << 1;
@@ -0,0 +1,79 @@
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
+    crate::NPM1300<I2c, Delay>
+{
+    /// Run a power sequence
+    ///
+    /// Executes the steps in order and stops at the first step that fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - The sequence to run, see [`PowerSequence`]
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PowerSequenceError`] naming the step that failed
+    pub async fn run_power_sequence(
+        &mut self,
+        sequence: &PowerSequence<'_>,
+    ) -> Result<(), PowerSequenceError<I2c::Error>> {
+        for (index, step) in sequence.steps().iter().enumerate() {
+            self.run_power_step(index, *step).await?;
+        }
+        Ok(())
+    }
+
+    /// Run a power sequence in reverse
+    ///
+    /// Executes the reverse of each step, from the last step to the first, and stops at the
+    /// first step that fails. See [`PowerStep::reversed`] for how steps are reversed.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - The sequence to run in reverse, see [`PowerSequence`]
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PowerSequenceError`] naming the reversed step that failed and the index
+    /// of the original step in the sequence
+    pub async fn run_power_sequence_reversed(
+        &mut self,
+        sequence: &PowerSequence<'_>,
+    ) -> Result<(), PowerSequenceError<I2c::Error>> {
+        for (index, step) in sequence.steps().iter().enumerate().rev() {
+            if let Some(step) = step.reversed() {
+                self.run_power_step(index, step).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a single power sequence step
+    async fn run_power_step(
+        &mut self,
+        index: usize,
+        step: PowerStep,
+    ) -> Result<(), PowerSequenceError<I2c::Error>> {
+        let result = match step {
+            PowerStep::EnableBuck(buck) => self.buck(buck).enable().await,
+            PowerStep::DisableBuck(buck) => self.buck(buck).disable().await,
+            PowerStep::EnableLoadSwitch(id) => self.enable_load_switch(id).await,
+            PowerStep::DisableLoadSwitch(id) => self.disable_load_switch(id).await,
+            PowerStep::SetBuckVoltage(buck, voltage) => {
+                self.buck(buck).set_normal_voltage(voltage).await
+            }
+            PowerStep::WaitMs(delay_ms) => {
+                self.delay.delay_ms(delay_ms).await;
+                Ok(())
+            }
+            PowerStep::WaitForPowerGood { buck, timeout_ms } => {
+                self.wait_for_buck_power_good(buck, timeout_ms).await
+            }
+        };
+        result.map_err(|error| PowerSequenceError { index, step, error })
+    }
+}
@@ -0,0 +1,73 @@
+use crate::buck::{BuckId, BuckVoltage};
+use crate::loadsw::LoadSwitchId;
+
+/// A single step of a [`PowerSequence`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum PowerStep {
+    /// Enable a BUCK regulator
+    EnableBuck(BuckId),
+    /// Disable a BUCK regulator
+    DisableBuck(BuckId),
+    /// Enable a load switch
+    EnableLoadSwitch(LoadSwitchId),
+    /// Disable a load switch
+    DisableLoadSwitch(LoadSwitchId),
+    /// Set a BUCK regulator normal mode output voltage
+    SetBuckVoltage(BuckId, BuckVoltage),
+    /// Wait for a number of milliseconds
+    WaitMs(u32),
+    /// Wait until a BUCK regulator reports power-good, failing after `timeout_ms` milliseconds
+    WaitForPowerGood { buck: BuckId, timeout_ms: u32 },
+}
+
+impl PowerStep {
+    /// Get the step undoing this step when the sequence is run in reverse
+    ///
+    /// Enabling and disabling a regulator or load switch undo each other and waits are kept,
+    /// so the delays between rails are also applied on teardown. Voltage changes and
+    /// power-good waits have no reverse step and return `None`.
+    pub fn reversed(&self) -> Option<PowerStep> {
+        match *self {
+            Self::EnableBuck(buck) => Some(Self::DisableBuck(buck)),
+            Self::DisableBuck(buck) => Some(Self::EnableBuck(buck)),
+            Self::EnableLoadSwitch(id) => Some(Self::DisableLoadSwitch(id)),
+            Self::DisableLoadSwitch(id) => Some(Self::EnableLoadSwitch(id)),
+            Self::SetBuckVoltage(..) => None,
+            Self::WaitMs(delay_ms) => Some(Self::WaitMs(delay_ms)),
+            Self::WaitForPowerGood { .. } => None,
+        }
+    }
+}
+
+/// An ordered list of power steps
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PowerSequence<'a> {
+    steps: &'a [PowerStep],
+}
+
+impl<'a> PowerSequence<'a> {
+    /// Create a power sequence from its steps
+    pub const fn new(steps: &'a [PowerStep]) -> Self {
+        Self { steps }
+    }
+
+    /// Get the steps of the sequence
+    pub fn steps(&self) -> &'a [PowerStep] {
+        self.steps
+    }
+}
+
+/// Error returned when a power sequence step fails
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[error("power sequence step {index} ({step:?}) failed: {error}")]
+pub struct PowerSequenceError<I2cError: core::fmt::Debug> {
+    /// Index of the failed step in the sequence
+    pub index: usize,
+    /// The step that failed, as executed
+    pub step: PowerStep,
+    /// The error returned by the step
+    pub error: crate::NPM1300Error<I2cError>,
+}
@@ -0,0 +1,277 @@
+use embedded_hal::digital::ErrorType;
+use embedded_hal_async::digital::Wait;
+
+/// Press classified by [`ShipHoldButton::wait_for_press`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum ShipHoldPress {
+    /// Pressed and released once
+    Short,
+    /// Held for at least [`ShipHoldButtonTimings::long_press_ms`]
+    Long,
+    /// Pressed twice within [`ShipHoldButtonTimings::double_press_window_ms`]
+    Double,
+}
+
+/// Ship hold button timings, all in milliseconds
+///
+/// Durations are counted in poll intervals, so they are approximate and exclude the I2C
+/// transfer time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ShipHoldButtonTimings {
+    /// Time the button must stay pressed for a press to be accepted
+    pub debounce_ms: u32,
+    /// Time the button must be held for a long press
+    pub long_press_ms: u32,
+    /// Time after a short press released within which a second press makes a double press
+    pub double_press_window_ms: u32,
+    /// Interval between SHPHLD event and pin status reads
+    pub poll_interval_ms: u32,
+}
+
+impl Default for ShipHoldButtonTimings {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 20,
+            long_press_ms: 1000,
+            double_press_window_ms: 300,
+            poll_interval_ms: 10,
+        }
+    }
+}
+
+/// Placeholder interrupt pin for a [`ShipHoldButton`] that polls without an interrupt line
+pub enum NoIrq {}
+
+impl ErrorType for NoIrq {
+    type Error = core::convert::Infallible;
+}
+
+impl Wait for NoIrq {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+}
+
+/// Ship hold (SHPHLD) button service
+///
+/// Classifies presses of the SHPHLD button from the SHPHLD event and pin status. While idle,
+/// the service awaits the PMIC interrupt line when an `Irq` pin is provided, otherwise it polls
+/// with the driver delay. The interrupt line must be routed to a GPIO configured as
+/// [`GpioMode::GpoIrq`](crate::gpios::GpioMode::GpoIrq) and is assumed to be active high.
+///
+/// Note that the PMIC may also use the button for long press reset, see
+/// [`NPM1300::disable_long_press_reset`](crate::NPM1300::disable_long_press_reset).
+pub struct ShipHoldButton<Irq: Wait = NoIrq> {
+    timings: ShipHoldButtonTimings,
+    irq: Option<Irq>,
+    /// A long press was reported while the button was still held
+    is_awaiting_release: bool,
+}
+
+impl ShipHoldButton<NoIrq> {
+    /// Create a new ship hold button service that polls while idle
+    ///
+    /// # Arguments
+    ///
+    /// * `timings` - The debounce and classification timings
+    pub fn new(timings: ShipHoldButtonTimings) -> Self {
+        Self {
+            timings,
+            irq: None,
+            is_awaiting_release: false,
+        }
+    }
+}
+
+impl<Irq: Wait> ShipHoldButton<Irq> {
+    /// Create a new ship hold button service driven by the PMIC interrupt line
+    ///
+    /// # Arguments
+    ///
+    /// * `timings` - The debounce and classification timings
+    /// * `irq` - The host pin connected to the PMIC interrupt line
+    pub fn with_irq(timings: ShipHoldButtonTimings, irq: Irq) -> Self {
+        Self {
+            timings,
+            irq: Some(irq),
+            is_awaiting_release: false,
+        }
+    }
+
+    /// Release the interrupt pin, if any
+    pub fn release(self) -> Option<Irq> {
+        self.irq
+    }
+
+    /// Get the debounce and classification timings
+    pub fn timings(&self) -> ShipHoldButtonTimings {
+        self.timings
+    }
+
+    /// Start the service
+    ///
+    /// Clears a stale SHPHLD event and enables the SHPHLD interrupt, which wakes
+    /// [`wait_for_press`](Self::wait_for_press) when an interrupt pin is provided.
+    pub async fn start<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        npm1300.clear_shphld_event().await?;
+        npm1300.enable_shphld_interrupt().await
+    }
+
+    /// Wait for the next button press and classify it
+    ///
+    /// A long press is reported as soon as the button has been held for
+    /// [`ShipHoldButtonTimings::long_press_ms`], the release is consumed by the next call.
+    /// A short press is reported once the double press window has expired.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ShipHoldPress)` - The classified press
+    /// * `Err(NPM1300Error)` - An error occurred while reading the button
+    pub async fn wait_for_press<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<ShipHoldPress, crate::NPM1300Error<I2c::Error>> {
+        if self.is_awaiting_release {
+            self.wait_for_release(npm1300, None).await?;
+            self.is_awaiting_release = false;
+        }
+
+        while !self.wait_for_debounced_press(npm1300, None).await? {}
+
+        let long_press_ms = self
+            .timings
+            .long_press_ms
+            .saturating_sub(self.timings.debounce_ms);
+        if !self.wait_for_release(npm1300, Some(long_press_ms)).await? {
+            self.is_awaiting_release = true;
+            return Ok(ShipHoldPress::Long);
+        }
+
+        if self
+            .wait_for_debounced_press(npm1300, Some(self.timings.double_press_window_ms))
+            .await?
+        {
+            self.wait_for_release(npm1300, None).await?;
+            return Ok(ShipHoldPress::Double);
+        }
+        Ok(ShipHoldPress::Short)
+    }
+
+    /// Wait for a press
+    ///
+    /// A press is detected from a latched SHPHLD event or from the pin, so that a press starting
+    /// while the interrupt line is held is not lost. Either way the button must still be down
+    /// after the debounce time.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout_ms` - Give up after this time, `None` to wait on the interrupt line if
+    ///   available
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - A press was detected
+    /// * `Ok(false)` - The press bounced, or no press started within `timeout_ms`
+    async fn wait_for_debounced_press<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+        timeout_ms: Option<u32>,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        let mut elapsed_ms = 0;
+        loop {
+            if npm1300.is_shphld_event_pending().await? {
+                npm1300.clear_shphld_event().await?;
+                break;
+            }
+            if npm1300.is_ship_hold_pressed().await? {
+                break;
+            }
+            match (timeout_ms, self.irq.as_mut()) {
+                (Some(timeout_ms), _) if elapsed_ms >= timeout_ms => return Ok(false),
+                (None, Some(irq)) => {
+                    irq.wait_for_high()
+                        .await
+                        .map_err(|_| crate::NPM1300Error::InterruptPin)?;
+                    // The interrupt line may be held by another event, so still pace the reads
+                    if npm1300.is_shphld_event_pending().await? {
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            npm1300.delay.delay_ms(self.timings.poll_interval_ms).await;
+            elapsed_ms += self.timings.poll_interval_ms;
+        }
+
+        npm1300.delay.delay_ms(self.timings.debounce_ms).await;
+        npm1300.is_ship_hold_pressed().await
+    }
+
+    /// Wait for the button to be released
+    ///
+    /// The button must stay released for the debounce time. The SHPHLD events raised by the
+    /// release, including contact bounce, are cleared afterwards so they are not taken for a
+    /// new press.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The button was released
+    /// * `Ok(false)` - The button was still held after `timeout_ms`
+    async fn wait_for_release<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+        timeout_ms: Option<u32>,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        let mut elapsed_ms = 0;
+        loop {
+            while npm1300.is_ship_hold_pressed().await? {
+                if timeout_ms.is_some_and(|timeout_ms| elapsed_ms >= timeout_ms) {
+                    return Ok(false);
+                }
+                npm1300.delay.delay_ms(self.timings.poll_interval_ms).await;
+                elapsed_ms += self.timings.poll_interval_ms;
+            }
+
+            // Let the contacts settle before dropping the events raised by the release
+            npm1300.delay.delay_ms(self.timings.debounce_ms).await;
+            elapsed_ms += self.timings.debounce_ms;
+            if !npm1300.is_ship_hold_pressed().await? {
+                npm1300.clear_shphld_event().await?;
+                return Ok(true);
+            }
+        }
+    }
+}
@@ -1,4 +1,19 @@
-use crate::{common::Task, field_sets::Shphldstatus, Shphldtim};
+mod button;
+mod types;
+
+// Re-export everything in types.rs
+pub use button::*;
+pub use types::*;
+
+use crate::{
+    common::Task,
+    field_sets::Shphldstatus,
+    gpios::{Gpio, GpioFunction},
+    Longtimresetdis, Longtimtwobuttonsel, Shphldpinstatus, Shphldtim,
+};
+
+/// Time to wait for the PMIC to power the host down after requesting a low power mode
+pub const LOW_POWER_MODE_ENTRY_TIMEOUT_MS: u32 = 100;
 
 impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
     crate::NPM1300<I2c, Delay>
@@ -21,6 +36,84 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Enter ship mode after checking that the PMIC can enter it
+    ///
+    /// See [`enter_low_power_mode`](Self::enter_low_power_mode).
+    ///
+    /// # Arguments
+    ///
+    /// * `press_timer` - How long SHPHLD must be pressed to wake the device
+    pub async fn enter_ship_mode_checked(
+        &mut self,
+        press_timer: Shphldtim,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.enter_low_power_mode(LowPowerMode::Ship, press_timer)
+            .await
+    }
+
+    /// Enter hibernate mode after checking that the PMIC can enter it
+    ///
+    /// See [`enter_low_power_mode`](Self::enter_low_power_mode).
+    ///
+    /// # Arguments
+    ///
+    /// * `press_timer` - How long SHPHLD must be pressed to wake the device
+    pub async fn enter_hibernate_mode_checked(
+        &mut self,
+        press_timer: Shphldtim,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.enter_low_power_mode(LowPowerMode::Hibernate, press_timer)
+            .await
+    }
+
+    /// Enter ship or hibernate mode after checking that the PMIC can enter it
+    ///
+    /// The PMIC ignores the request while VBUS is present, so VBUS is checked first. The SHPHLD
+    /// press timer is then configured and all pending events are cleared so that a stale
+    /// event cannot wake the device straight away.
+    ///
+    /// The device always wakes on a SHPHLD press or a VBUS connection. The hibernate wake-up
+    /// timer is not part of the register map supported by this driver, so it is left at its
+    /// current configuration.
+    ///
+    /// When the mode is entered the PMIC cuts power to the host and this function does not
+    /// return.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The low power mode to enter
+    /// * `press_timer` - How long SHPHLD must be pressed to wake the device
+    ///
+    /// # Errors
+    ///
+    /// * `NPM1300Error::VbusPresent` - VBUS is connected
+    /// * `NPM1300Error::LowPowerModeNotEntered` - The host was still powered
+    ///   [`LOW_POWER_MODE_ENTRY_TIMEOUT_MS`] after the request
+    pub async fn enter_low_power_mode(
+        &mut self,
+        mode: LowPowerMode,
+        press_timer: Shphldtim,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if self.get_vbus_in_status().await?.is_vbus_in_present {
+            return Err(crate::NPM1300Error::VbusPresent);
+        }
+
+        self.set_ship_hold_press_timer(press_timer).await?;
+        self.clear_all_events().await?;
+
+        match mode {
+            LowPowerMode::Ship => self.enter_ship_mode().await?,
+            LowPowerMode::Hibernate => self.enter_hibernate_mode().await?,
+        }
+
+        // The PMIC powers the host down, so still running means the request was ignored
+        self.delay.delay_ms(LOW_POWER_MODE_ENTRY_TIMEOUT_MS).await;
+        if self.get_vbus_in_status().await?.is_vbus_in_present {
+            return Err(crate::NPM1300Error::VbusPresent);
+        }
+        Err(crate::NPM1300Error::LowPowerModeNotEntered(mode))
+    }
+
     /// Request a reset of the ship hold configuration
     pub async fn reset_ship_hold_config(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         self.device
@@ -42,11 +135,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await?;
 
         // Load the new configuration
-        self.device
-            .ship()
-            .taskshphldcfgstrobe()
-            .dispatch_async(|command| command.set_taskshphldconfigstrobe(Task::Trigger))
-            .await
+        self.strobe_ship_hold_config().await
     }
 
     /// Get the ship hold status
@@ -58,20 +147,23 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device.ship().shphldstatus().read_async().await
     }
 
+    /// Check whether the ship hold button is pressed
+    ///
+    /// The SHPHLD pin is active low.
+    pub async fn is_ship_hold_pressed(&mut self) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        Ok(self.get_ship_hold_status().await?.shphldpinstatus() == Shphldpinstatus::Low)
+    }
+
     /// Enable the long press reset
     pub async fn enable_long_press_reset(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         self.device
             .ship()
             .lpresetconfig()
-            .modify_async(|reg| reg.set_longtimresetdis(crate::Longtimresetdis::Enabled))
+            .modify_async(|reg| reg.set_longtimresetdis(Longtimresetdis::Enabled))
             .await?;
 
         // Load the new configuration
-        self.device
-            .ship()
-            .taskshphldcfgstrobe()
-            .dispatch_async(|command| command.set_taskshphldconfigstrobe(Task::Trigger))
-            .await
+        self.strobe_ship_hold_config().await
     }
 
     /// Disable the long press reset
@@ -81,15 +173,90 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .ship()
             .lpresetconfig()
-            .modify_async(|reg| reg.set_longtimresetdis(crate::Longtimresetdis::Disabled))
+            .modify_async(|reg| reg.set_longtimresetdis(Longtimresetdis::Disabled))
             .await?;
 
         // Load the new configuration
+        self.strobe_ship_hold_config().await
+    }
+
+    /// Apply the ship hold button configuration
+    ///
+    /// All settings are written before a single configuration strobe, so they take effect
+    /// together.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The ship hold button configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if `two_button` is set and GPIO0 is assigned to
+    /// another function
+    pub async fn apply_ship_hold_config(
+        &mut self,
+        config: ShipHoldConfig,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if config.two_button {
+            self.check_gpio_available(Gpio::Gpio0, GpioFunction::ShipHoldReset)?;
+        }
+
         self.device
             .ship()
-            .taskshphldcfgstrobe()
-            .dispatch_async(|command| command.set_taskshphldconfigstrobe(Task::Trigger))
-            .await
+            .shphldconfig()
+            .write_async(|reg| reg.set_shphldtim(config.press_timer))
+            .await?;
+        self.device
+            .ship()
+            .lpresetconfig()
+            .write_async(|reg| {
+                reg.set_longtimresetdis(if config.long_press_reset {
+                    Longtimresetdis::Enabled
+                } else {
+                    Longtimresetdis::Disabled
+                });
+                reg.set_longtimtwobuttonsel(if config.two_button {
+                    Longtimtwobuttonsel::Shphldgpio0
+                } else {
+                    Longtimtwobuttonsel::Shphld
+                });
+            })
+            .await?;
+
+        // Load the new configuration
+        self.strobe_ship_hold_config().await?;
+
+        // Only track GPIO0 once the configuration is loaded
+        if config.two_button {
+            self.claim_gpio(Gpio::Gpio0, GpioFunction::ShipHoldReset)?;
+        } else {
+            self.release_gpio_function(GpioFunction::ShipHoldReset);
+        }
+        Ok(())
+    }
+
+    /// Get the ship hold button configuration
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ShipHoldConfig)` - The configured ship hold button settings
+    /// * `Err(NPM1300Error)` - An error occurred while reading the configuration
+    pub async fn get_ship_hold_config(
+        &mut self,
+    ) -> Result<ShipHoldConfig, crate::NPM1300Error<I2c::Error>> {
+        let press_timer = self
+            .device
+            .ship()
+            .shphldconfig()
+            .read_async()
+            .await?
+            .shphldtim();
+        let lpreset = self.device.ship().lpresetconfig().read_async().await?;
+        Ok(ShipHoldConfig {
+            press_timer,
+            long_press_reset: lpreset.longtimresetdis() == Longtimresetdis::Enabled,
+            two_button: lpreset.longtimtwobuttonsel() == Longtimtwobuttonsel::Shphldgpio0,
+        })
     }
 
     /// Use the ship hold button only
@@ -99,30 +266,37 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .ship()
             .lpresetconfig()
-            .modify_async(|reg| reg.set_longtimtwobuttonsel(crate::Longtimtwobuttonsel::Shphld))
+            .modify_async(|reg| reg.set_longtimtwobuttonsel(Longtimtwobuttonsel::Shphld))
             .await?;
 
         // Load the new configuration
-        self.device
-            .ship()
-            .taskshphldcfgstrobe()
-            .dispatch_async(|command| command.set_taskshphldconfigstrobe(Task::Trigger))
-            .await
+        self.strobe_ship_hold_config().await?;
+        self.release_gpio_function(GpioFunction::ShipHoldReset);
+        Ok(())
     }
 
     /// Use the ship hold button and GPIO0 to perform long press reset
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::GpioAlreadyInUse` if GPIO0 is assigned to another function
     pub async fn use_ship_hold_button_and_gpio0(
         &mut self,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.check_gpio_available(Gpio::Gpio0, GpioFunction::ShipHoldReset)?;
         self.device
             .ship()
             .lpresetconfig()
-            .modify_async(|reg| {
-                reg.set_longtimtwobuttonsel(crate::Longtimtwobuttonsel::Shphldgpio0)
-            })
+            .modify_async(|reg| reg.set_longtimtwobuttonsel(Longtimtwobuttonsel::Shphldgpio0))
             .await?;
 
         // Load the new configuration
+        self.strobe_ship_hold_config().await?;
+        self.claim_gpio(Gpio::Gpio0, GpioFunction::ShipHoldReset)
+    }
+
+    /// Load the ship hold configuration written to SHPHLDCONFIG and LPRESETCONFIG
+    async fn strobe_ship_hold_config(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         self.device
             .ship()
             .taskshphldcfgstrobe()
@@ -0,0 +1,23 @@
+/// Low power mode entered through the ship block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LowPowerMode {
+    /// Ship mode, woken by a SHPHLD press or VBUS connection
+    Ship,
+    /// Hibernate mode, ship mode with a wake-up timer
+    Hibernate,
+}
+
+/// Ship hold button configuration
+///
+/// See [`NPM1300::apply_ship_hold_config`](crate::NPM1300::apply_ship_hold_config).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ShipHoldConfig {
+    /// How long SHPHLD must be pressed to wake the device from ship or hibernate mode
+    pub press_timer: crate::Shphldtim,
+    /// Indicates if the 10 s long press reset is enabled
+    pub long_press_reset: bool,
+    /// Indicates if the long press reset requires both SHPHLD and GPIO0
+    pub two_button: bool,
+}
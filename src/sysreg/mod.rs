@@ -1,9 +1,11 @@
 mod types;
+mod vbus_manager;
 
 // Re-export everything in types.rs
 pub use types::*;
+pub use vbus_manager::*;
 
-use crate::{common::Task, Vbussuspendena};
+use crate::{common::Task, mainreg::Vbusin1EventMask, Vbussuspendena};
 
 impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
     crate::NPM1300<I2c, Delay>
@@ -76,6 +78,73 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Get VBUS input current limit
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(VbusInCurrentLimit)` - The input current limit (VBUSINILIM0)
+    /// * `Err(NPM1300Error)` - An error occurred while reading the current limit
+    pub async fn get_vbus_in_current_limit(
+        &mut self,
+    ) -> Result<VbusInCurrentLimit, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .device
+            .vbusin()
+            .vbusinilim_0()
+            .read_async()
+            .await?
+            .vbusinilim_0())
+    }
+
+    /// Get VBUS input startup current limit
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(VbusInCurrentLimit)` - The input startup current limit (VBUSINILIMSTARTUP)
+    /// * `Err(NPM1300Error)` - An error occurred while reading the current limit
+    pub async fn get_vbus_in_startup_current_limit(
+        &mut self,
+    ) -> Result<VbusInCurrentLimit, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .device
+            .vbusin()
+            .vbusinilimstartup()
+            .read_async()
+            .await?
+            .vbusinilimstartup())
+    }
+
+    /// Get VBUS suspend mode
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if VBUS suspend mode is enabled, `false` otherwise
+    /// * `Err(NPM1300Error)` - An error occurred while reading the suspend mode
+    pub async fn get_vbus_mode(&mut self) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        let suspend = self.device.vbusin().vbussuspend().read_async().await?;
+        Ok(suspend.vbussuspendena() == Vbussuspendena::Suspend)
+    }
+
+    /// Get a snapshot of the VBUS input state
+    ///
+    /// Reads the VBUS status, CC status, current limits and suspend mode, and measures
+    /// the VBUS voltage.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(VbusState)` - The VBUS input state
+    /// * `Err(NPM1300Error)` - An error occurred while reading the VBUS state
+    pub async fn get_vbus_state(&mut self) -> Result<VbusState, crate::NPM1300Error<I2c::Error>> {
+        Ok(VbusState {
+            status: self.get_vbus_in_status().await?,
+            cc_status: self.get_vbus_cc_status().await?,
+            current_limit: self.get_vbus_in_current_limit().await?,
+            startup_current_limit: self.get_vbus_in_startup_current_limit().await?,
+            is_suspend_enabled: self.get_vbus_mode().await?,
+            vbus_v: self.measure_vbus().await?,
+        })
+    }
+
     /// Get VBUS CC comparator flags status
     ///
     /// # Returns
@@ -112,4 +181,59 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             is_vbus_in_overvoltage_protection_active: status.vbusinovrprotactive() == 1,
         })
     }
+
+    /// Apply the VBUS input current limit advertised on the USB-C CC lines
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(VbusInCurrentLimit)` - The applied current limit, see
+    ///   [`UsbCCurrentAdvertisement::current_limit`]
+    /// * `Err(NPM1300Error)` - An error occurred while reading the CC status or setting the limit
+    pub async fn apply_usb_c_current_limit(
+        &mut self,
+    ) -> Result<VbusInCurrentLimit, crate::NPM1300Error<I2c::Error>> {
+        let current_limit = self
+            .get_vbus_cc_status()
+            .await?
+            .advertisement()
+            .current_limit();
+        self.set_vbus_in_current_limit(current_limit).await?;
+        Ok(current_limit)
+    }
+
+    /// Enable automatic USB-C input current negotiation
+    ///
+    /// Enables the CC1/CC2 state change interrupts and applies the currently advertised
+    /// limit. Call [`handle_usb_c_cc_events`](Self::handle_usb_c_cc_events) when the PMIC
+    /// interrupt fires to follow later changes.
+    pub async fn enable_usb_c_current_negotiation(
+        &mut self,
+    ) -> Result<VbusInCurrentLimit, crate::NPM1300Error<I2c::Error>> {
+        let cc_events = Vbusin1EventMask::CC1_STATE_CHANGE | Vbusin1EventMask::CC2_STATE_CHANGE;
+        self.clear_vbusin1_event_mask(cc_events).await?;
+        self.enable_vbusin1_interrupts(cc_events).await?;
+        self.apply_usb_c_current_limit().await
+    }
+
+    /// Handle pending USB-C CC state change events
+    ///
+    /// Clears the CC1/CC2 state change events and applies the newly advertised limit.
+    /// Other VBUSIN1 events are left pending.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(VbusInCurrentLimit))` - A CC event was pending and this limit was applied
+    /// * `Ok(None)` - No CC event was pending
+    /// * `Err(NPM1300Error)` - An error occurred while handling the events
+    pub async fn handle_usb_c_cc_events(
+        &mut self,
+    ) -> Result<Option<VbusInCurrentLimit>, crate::NPM1300Error<I2c::Error>> {
+        let cc_events = self.get_vbusin1_events().await?
+            & (Vbusin1EventMask::CC1_STATE_CHANGE | Vbusin1EventMask::CC2_STATE_CHANGE);
+        if cc_events.is_empty() {
+            return Ok(None);
+        }
+        self.clear_vbusin1_event_mask(cc_events).await?;
+        Ok(Some(self.apply_usb_c_current_limit().await?))
+    }
 }
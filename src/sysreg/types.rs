@@ -1,5 +1,5 @@
 /// Input current limit for VBUS
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum VbusInCurrentLimit {
     // 500 mA
@@ -36,6 +36,29 @@ pub enum VbusInCurrentLimit {
     MA1500 = 15,
 }
 
+impl VbusInCurrentLimit {
+    /// Get the current limit in milliamps (mA)
+    pub fn current_limit_ma(&self) -> u16 {
+        match self {
+            Self::MA100 => 100,
+            Self::MA200 => 200,
+            Self::MA300 => 300,
+            Self::MA400 => 400,
+            Self::MA500 | Self::MA500Alt => 500,
+            Self::MA600 => 600,
+            Self::MA700 => 700,
+            Self::MA800 => 800,
+            Self::MA900 => 900,
+            Self::MA1000 => 1000,
+            Self::MA1100 => 1100,
+            Self::MA1200 => 1200,
+            Self::MA1300 => 1300,
+            Self::MA1400 => 1400,
+            Self::MA1500 => 1500,
+        }
+    }
+}
+
 // Add conversion from u8
 impl From<u8> for VbusInCurrentLimit {
     fn from(value: u8) -> Self {
@@ -69,7 +92,7 @@ impl From<VbusInCurrentLimit> for u8 {
 }
 
 /// Input current limit for VBUS
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum VbusInCcCmp {
     /// No connection
@@ -103,13 +126,57 @@ impl From<VbusInCcCmp> for u8 {
 }
 
 /// VBUS CC comparator status flags
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct VbusCcStatus {
     pub vbusin_cc1_status: VbusInCcCmp,
     pub vbusin_cc2_status: VbusInCcCmp,
 }
 
+impl VbusCcStatus {
+    /// Get the current advertised by the USB-C source
+    ///
+    /// Only one CC line is connected to the source, the other one reports no connection.
+    pub fn advertisement(&self) -> UsbCCurrentAdvertisement {
+        match self.vbusin_cc1_status.max(self.vbusin_cc2_status) {
+            VbusInCcCmp::NoConnection => UsbCCurrentAdvertisement::NoConnection,
+            VbusInCcCmp::DefaultUsb => UsbCCurrentAdvertisement::Default,
+            VbusInCcCmp::MA1500HighPower => UsbCCurrentAdvertisement::MA1500,
+            VbusInCcCmp::MA3000HighPower => UsbCCurrentAdvertisement::MA3000,
+        }
+    }
+}
+
+/// Current advertised by a USB-C source on the CC lines
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum UsbCCurrentAdvertisement {
+    /// No USB-C source detected
+    NoConnection,
+    /// Default USB power, 500 mA for USB 2.0
+    Default,
+    /// 1.5 A
+    MA1500,
+    /// 3.0 A
+    MA3000,
+}
+
+impl UsbCCurrentAdvertisement {
+    /// Get the VBUS input current limit matching the advertisement
+    ///
+    /// Without a USB-C source the startup limit of 100 mA is kept, and the 3.0 A
+    /// advertisement is capped at the highest supported limit of 1500 mA.
+    pub fn current_limit(&self) -> VbusInCurrentLimit {
+        match self {
+            Self::NoConnection => VbusInCurrentLimit::MA100,
+            Self::Default => VbusInCurrentLimit::MA500,
+            Self::MA1500 | Self::MA3000 => VbusInCurrentLimit::MA1500,
+        }
+    }
+}
+
 /// VBUS status flags
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct VbusInStatus {
     /// Indicates if VBUS is detected
@@ -149,3 +216,21 @@ pub struct VbusInStatus {
     /// - `false`: VBUS output is not active
     pub is_vbus_out_active: bool,
 }
+
+/// Snapshot of the VBUS input state
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct VbusState {
+    /// VBUS status flags
+    pub status: VbusInStatus,
+    /// VBUS CC comparator status flags
+    pub cc_status: VbusCcStatus,
+    /// Input current limit (VBUSINILIM0)
+    pub current_limit: VbusInCurrentLimit,
+    /// Input startup current limit (VBUSINILIMSTARTUP)
+    pub startup_current_limit: VbusInCurrentLimit,
+    /// Indicates if VBUS suspend mode is enabled (VBUSSUSPEND)
+    pub is_suspend_enabled: bool,
+    /// Measured VBUS voltage in volts
+    pub vbus_v: f32,
+}
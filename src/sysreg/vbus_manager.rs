@@ -0,0 +1,178 @@
+use super::VbusInCurrentLimit;
+use crate::common::Clock;
+use crate::mainreg::Vbusin0EventMask;
+
+/// How the VBUS input current limit is chosen when VBUS is attached
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum VbusCurrentLimitPolicy {
+    /// Always apply the same current limit
+    Fixed(VbusInCurrentLimit),
+    /// Apply the current advertised on the USB-C CC lines, see
+    /// [`NPM1300::apply_usb_c_current_limit`](crate::NPM1300::apply_usb_c_current_limit)
+    UsbC,
+}
+
+/// A completed VBUS attach session
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct VbusSession {
+    /// Time VBUS was attached in milliseconds
+    pub attached_at_ms: u64,
+    /// Time VBUS was detached in milliseconds
+    pub detached_at_ms: u64,
+    /// Indicates if VBUS was attached and detached between two updates, `attached_at_ms` is
+    /// then the time of the previous update
+    pub is_missed: bool,
+}
+
+impl VbusSession {
+    /// Duration of the session in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        self.detached_at_ms.saturating_sub(self.attached_at_ms)
+    }
+}
+
+/// Result of a [`VbusManager::update`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct VbusUpdate {
+    /// VBUS was attached and this current limit was applied
+    pub attached: Option<VbusInCurrentLimit>,
+    /// VBUS was detached, ending this session
+    pub detached: Option<VbusSession>,
+    /// Indicates if VBUS over-voltage protection is active or was triggered since the previous
+    /// update
+    pub is_overvoltage: bool,
+    /// Indicates if VBUS under-voltage is detected or was detected since the previous update
+    pub is_undervoltage: bool,
+}
+
+/// VBUS attach/detach manager
+///
+/// Tracks the VBUS connection and re-applies the input current limit on every attach, since
+/// the PMIC reverts to the 100 mA startup limit whenever VBUS is replugged. Sessions are
+/// timestamped with the injected [`Clock`].
+pub struct VbusManager<C: Clock> {
+    clock: C,
+    policy: VbusCurrentLimitPolicy,
+    /// Time the current session started in milliseconds, `None` while detached
+    attached_at_ms: Option<u64>,
+    /// Time of the previous update in milliseconds
+    updated_at_ms: u64,
+}
+
+impl<C: Clock> VbusManager<C> {
+    /// Create a new VBUS manager
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Clock used to timestamp sessions
+    /// * `policy` - How the current limit is chosen on attach
+    pub fn new(clock: C, policy: VbusCurrentLimitPolicy) -> Self {
+        Self {
+            clock,
+            policy,
+            attached_at_ms: None,
+            updated_at_ms: 0,
+        }
+    }
+
+    /// Check whether VBUS is attached
+    pub fn is_attached(&self) -> bool {
+        self.attached_at_ms.is_some()
+    }
+
+    /// Get the time the current session started in milliseconds, `None` while detached
+    pub fn attached_at_ms(&self) -> Option<u64> {
+        self.attached_at_ms
+    }
+
+    /// Start managing VBUS
+    ///
+    /// Enables the VBUSIN0 interrupts and handles a VBUS already attached at startup. Call
+    /// [`update`](Self::update) when the PMIC interrupt fires afterwards.
+    pub async fn start<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<VbusUpdate, crate::NPM1300Error<I2c::Error>> {
+        npm1300
+            .enable_vbusin0_interrupts(Vbusin0EventMask::all())
+            .await?;
+        self.update(npm1300).await
+    }
+
+    /// Handle pending VBUSIN0 events
+    ///
+    /// Clears the VBUSIN0 events and reconciles the tracked connection with the events and the
+    /// VBUS status, so that an attach and detach happening between two updates are both
+    /// reported. A session that started and ended between two updates is reported as detached
+    /// with [`VbusSession::is_missed`] set. Over- and under-voltage events latched since the
+    /// previous update are reported even if the condition is gone.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(VbusUpdate)` - The attach, detach and over/under-voltage state
+    /// * `Err(NPM1300Error)` - An error occurred while handling the events
+    pub async fn update<
+        I2c: embedded_hal_async::i2c::I2c,
+        Delay: embedded_hal_async::delay::DelayNs,
+    >(
+        &mut self,
+        npm1300: &mut crate::NPM1300<I2c, Delay>,
+    ) -> Result<VbusUpdate, crate::NPM1300Error<I2c::Error>> {
+        let events = npm1300.get_vbusin0_events().await?;
+        if !events.is_empty() {
+            npm1300.clear_vbusin0_event_mask(events).await?;
+        }
+        let status = npm1300.get_vbus_in_status().await?;
+        let now_ms = self.clock.now_ms();
+        let updated_at_ms = core::mem::replace(&mut self.updated_at_ms, now_ms);
+
+        let mut detached = None;
+        if let Some(attached_at_ms) = self.attached_at_ms {
+            if events.contains(Vbusin0EventMask::VBUS_REMOVED) || !status.is_vbus_in_present {
+                detached = Some(VbusSession {
+                    attached_at_ms,
+                    detached_at_ms: now_ms,
+                    is_missed: false,
+                });
+                self.attached_at_ms = None;
+            }
+        } else if events.contains(Vbusin0EventMask::VBUS_DETECTED | Vbusin0EventMask::VBUS_REMOVED)
+            && !status.is_vbus_in_present
+        {
+            // VBUS came and went since the previous update, only the events show it
+            detached = Some(VbusSession {
+                attached_at_ms: updated_at_ms,
+                detached_at_ms: now_ms,
+                is_missed: true,
+            });
+        }
+
+        let mut attached = None;
+        if status.is_vbus_in_present && self.attached_at_ms.is_none() {
+            let current_limit = match self.policy {
+                VbusCurrentLimitPolicy::Fixed(current_limit) => {
+                    npm1300.set_vbus_in_current_limit(current_limit).await?;
+                    current_limit
+                }
+                VbusCurrentLimitPolicy::UsbC => npm1300.apply_usb_c_current_limit().await?,
+            };
+            attached = Some(current_limit);
+            self.attached_at_ms = Some(now_ms);
+        }
+
+        Ok(VbusUpdate {
+            attached,
+            detached,
+            is_overvoltage: status.is_vbus_in_overvoltage_protection_active
+                || events.contains(Vbusin0EventMask::OVRVOLT_DETECTED),
+            is_undervoltage: status.is_vbus_undervoltage_detected
+                || events.contains(Vbusin0EventMask::UNDERVOLT_DETECTED),
+        })
+    }
+}